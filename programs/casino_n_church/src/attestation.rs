@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::errors::CasinoError;
+
+/// Ed25519 program data layout for one signature: a 2-byte header, 14 bytes of
+/// offsets, then the public key, signature and message
+const HEADER_LEN: usize = 16;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Require the instruction just before this one to be an Ed25519 program check of
+/// `verifier`'s signature over exactly `message`.
+/// The precompile has already verified the signature; this only checks it signed what we expect.
+/// Inco's `is_validsignature` (see `verify_decrypted_payout`) only accepts covalidator
+/// signatures over decrypted handles, so it can't stand in for an arbitrary verifier key.
+pub fn verify_ed25519_attestation(
+    instructions: &AccountInfo,
+    verifier: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, CasinoError::InstructionSysvarNotFound);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+
+    require_keys_eq!(ix.program_id, ed25519_program::ID, CasinoError::Ed25519Program);
+    require!(ix.accounts.is_empty(), CasinoError::Ed25519Accounts);
+    require!(
+        ix.data.len() == HEADER_LEN + PUBKEY_LEN + SIGNATURE_LEN + message.len(),
+        CasinoError::Ed25519DataLength
+    );
+    require!(ix.data[0] == 1, CasinoError::Ed25519Signature);
+
+    // Every offset must point into this instruction's own data (index u16::MAX)
+    let read_u16 = |at: usize| u16::from_le_bytes([ix.data[at], ix.data[at + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        CasinoError::Ed25519Signature
+    );
+    require!(
+        signature_offset == HEADER_LEN + PUBKEY_LEN && public_key_offset == HEADER_LEN,
+        CasinoError::Ed25519Signature
+    );
+
+    require!(
+        ix.data[public_key_offset..public_key_offset + PUBKEY_LEN] == verifier.to_bytes(),
+        CasinoError::Ed25519Pubkey
+    );
+    require!(
+        message_offset == HEADER_LEN + PUBKEY_LEN + SIGNATURE_LEN
+            && message_size == message.len()
+            && ix.data[message_offset..] == *message,
+        CasinoError::Ed25519Message
+    );
+    Ok(())
+}
@@ -40,4 +40,62 @@ pub enum CasinoError {
     InsufficientVaultFunds,
     #[msg("Invalid payout amount")]
     InvalidPayoutAmount,
+    #[msg("Handle does not match the game's payout handle")]
+    PayoutHandleMismatch,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid config value")]
+    InvalidConfig,
+    #[msg("Admin account is already in the current layout")]
+    AlreadyMigrated,
+    #[msg("Invalid game id")]
+    InvalidGameId,
+    #[msg("Game is paused")]
+    GamePaused,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Multi-bet roulette games must close their legs account")]
+    MissingRouletteLegs,
+    #[msg("Game payout is not zero")]
+    GameNotLost,
+    #[msg("LP principal accounting mismatch")]
+    LpAccountingMismatch,
+    #[msg("Bet's maximum payout exceeds the house exposure limit")]
+    ExposureLimitExceeded,
+    #[msg("Open games' maximum payouts would no longer be covered")]
+    LiabilityNotCovered,
+    #[msg("Withdrawal delay has not elapsed")]
+    WithdrawalCooldown,
+    #[msg("Withdraw request is not at the head of the queue")]
+    NotQueueHead,
+    #[msg("Liquidity cannot fill this all-or-nothing withdrawal yet")]
+    WithdrawNotFillable,
+    #[msg("Withdraw request was cancelled")]
+    WithdrawCancelled,
+    #[msg("Only an all-or-nothing withdrawal liquidity cannot fill may be requeued")]
+    NotRequeueable,
+    #[msg("Mint is not accepted for betting")]
+    MintNotAccepted,
+    #[msg("Game was not played in this currency")]
+    CurrencyMismatch,
+    #[msg("A balance withdrawal is already pending")]
+    WithdrawPending,
+    #[msg("No balance withdrawal is pending")]
+    NoPendingWithdraw,
+    #[msg("Quest deadline has passed")]
+    QuestExpired,
+    #[msg("Quest has no rewards left")]
+    QuestFull,
+    #[msg("URI is too long")]
+    UriTooLong,
+    #[msg("Submission already approved")]
+    AlreadyApproved,
+    #[msg("Quest is still open")]
+    QuestStillOpen,
+    #[msg("Boost voucher does not apply to this bet")]
+    InvalidBoost,
+    #[msg("Not enough FAITH")]
+    InsufficientFaith,
+    #[msg("Promo budget cannot cover this free bet")]
+    InsufficientPromoBudget,
 }
@@ -0,0 +1,305 @@
+use anchor_lang::prelude::*;
+
+use crate::states::BoostKind;
+
+#[event]
+pub struct AdminMigrated {
+    pub admin: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub authority: Pubkey,
+    /// Defaults for games created afterwards; existing GameConfigs are unchanged
+    pub old_default_house_edge_bps: u16,
+    pub new_default_house_edge_bps: u16,
+    pub old_quest_creation_fee: u64,
+    pub new_quest_creation_fee: u64,
+    pub default_min_bet: u64,
+    pub default_max_bet: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GameConfigUpdated {
+    pub admin: Pubkey,
+    pub game_id: u8,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub house_edge_bps: u16,
+    pub straight_multiplier: u64,
+    pub jackpot_multiplier: u64,
+    pub small_win_multiplier: u64,
+    pub max_multiplier_bps: u64,
+    pub paused: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub admin: Pubkey,
+    pub signer: Pubkey,
+    pub paused: bool,
+    pub pause_flags: u8,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub admin: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct VaultConfigUpdated {
+    pub admin: Pubkey,
+    pub reserve_target: u64,
+    pub treasury_share_bps: u16,
+    pub max_exposure_bps: u16,
+    pub slot: u64,
+}
+
+#[event]
+pub struct VaultsRebalanced {
+    pub admin: Pubkey,
+    /// LP capital moved into casino_vault to restore the reserve
+    pub refilled: u64,
+    /// Earlier refills returned to the LP vault
+    pub float_repaid: u64,
+    pub profit_to_lp: u64,
+    pub profit_to_treasury: u64,
+    pub casino_balance: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub admin: Pubkey,
+    pub old_protocol_fee_bps: u16,
+    pub new_protocol_fee_bps: u16,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WithdrawDelayUpdated {
+    pub admin: Pubkey,
+    pub old_delay_slots: u64,
+    pub new_delay_slots: u64,
+}
+
+#[event]
+pub struct WithdrawRequested {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub request_id: u64,
+    pub shares: u64,
+    pub allow_partial: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WithdrawExecuted {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub request_id: u64,
+    pub shares_burned: u64,
+    pub lamports: u64,
+    /// Cost basis returned; lamports above it are yield, below it a realized loss
+    pub principal: u64,
+    /// Shares still queued on this request; zero once it is closed
+    pub shares_remaining: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WithdrawCancelled {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub request_id: u64,
+    pub shares: u64,
+    /// Cost basis returned to the owner's `LiquidityProvider`
+    pub principal: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WithdrawRequeued {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub old_request_id: u64,
+    /// Id the request now holds at the back of the queue
+    pub request_id: u64,
+    pub shares: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub enabled: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TokenVaultWithdrawn {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// Tokens left in the vault
+    pub remaining: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct BalanceDeposited {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct BalanceWithdrawn {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    /// Lamports requested; paid out only if `approved`
+    pub amount: u64,
+    /// Whether the encrypted balance covered the request
+    pub approved: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WritingQuestCreated {
+    pub admin: Pubkey,
+    pub quest: Pubkey,
+    pub creator: Pubkey,
+    pub curator: Pubkey,
+    pub faith_reward: u64,
+    pub max_approvals: u32,
+    pub deadline_slot: u64,
+    /// `Admin::quest_creation_fee` paid to the treasury
+    pub fee: u64,
+    /// `Admin::quest_faith_backing` paid into the promo budget
+    pub faith_backing: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SermonSubmitted {
+    pub quest: Pubkey,
+    pub author: Pubkey,
+    pub content_hash: [u8; 32],
+    pub slot: u64,
+}
+
+#[event]
+pub struct SubmissionApproved {
+    pub quest: Pubkey,
+    pub author: Pubkey,
+    pub faith: u64,
+    /// Author's FAITH after the reward
+    pub total_faith: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct BlinkQuestCreated {
+    pub admin: Pubkey,
+    pub quest: Pubkey,
+    pub creator: Pubkey,
+    pub verifier: Pubkey,
+    pub faith_reward: u64,
+    pub bounty: u64,
+    pub max_completions: u32,
+    pub deadline_slot: u64,
+    /// `Admin::quest_creation_fee` paid to the treasury
+    pub fee: u64,
+    /// `Admin::quest_faith_backing` paid into the promo budget
+    pub faith_backing: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct QuestVerifierUpdated {
+    pub admin: Pubkey,
+    pub verifier: Pubkey,
+    pub enabled: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct BlinkQuestCompleted {
+    pub quest: Pubkey,
+    pub completer: Pubkey,
+    pub faith: u64,
+    pub bounty: u64,
+    /// Completer's FAITH after the reward
+    pub total_faith: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PromoBudgetFunded {
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FaithRedeemed {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub kind: BoostKind,
+    pub value: u64,
+    pub max_stake: u64,
+    pub faith_cost: u64,
+    /// Owner's FAITH after the redemption
+    pub total_faith: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct BoostCancelled {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub kind: BoostKind,
+    pub value: u64,
+    /// Free bet credit returned to the promo budget
+    pub refunded: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FaithMintCreated {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FaithDecayed {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    /// FAITH burned or withheld from a reward for inactivity in the Casino
+    pub amount: u64,
+    /// Decay the owner's FAITH couldn't cover yet
+    pub still_owed: u64,
+    pub slot: u64,
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{burn, mint_to, Burn, MintTo, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::CasinoError;
+use crate::events::FaithDecayed;
+use crate::states::{Admin, PlayerProfile};
+
+/// FAITH is a whole-unit Token-2022 mint at ["faith_mint", admin].
+/// The admin PDA is its mint authority; FAITH is only ever burned with its holder's signature.
+pub const FAITH_DECIMALS: u8 = 0;
+
+/// Mint `amount` FAITH into `to`, signed by the admin PDA
+pub fn mint_faith<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    admin: &Account<'info, Admin>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"admin", admin.authority.as_ref(), &[admin.bump]]];
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: admin.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+/// Burn `amount` FAITH from `from`, signed by its owner
+pub fn burn_faith<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    owner: &Signer<'info>,
+    amount: u64,
+) -> Result<()> {
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: mint.to_account_info(),
+                from: from.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Burn the decay `profile` owes from its owner's FAITH account `holder`, as far as the
+/// balance covers it; the rest stays owed. Returns the FAITH left in `holder`.
+pub fn burn_faith_decay<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &InterfaceAccount<'info, Mint>,
+    holder: &InterfaceAccount<'info, TokenAccount>,
+    owner: &Signer<'info>,
+    admin: &Account<'info, Admin>,
+    profile: &mut PlayerProfile,
+    now_slot: u64,
+) -> Result<u64> {
+    let decayed = profile.take_faith_decay(now_slot, holder.amount);
+    if decayed > 0 {
+        burn_faith(token_program, mint, holder, owner, decayed)?;
+        emit_faith_decayed(admin, profile, decayed, now_slot);
+    }
+    Ok(holder.amount - decayed)
+}
+
+/// Mint a `reward` of FAITH into its owner's account `to`, withholding whatever decay
+/// `profile` owes. Returns `to`'s new balance, which decay is charged on from now.
+pub fn mint_faith_reward<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    admin: &Account<'info, Admin>,
+    profile: &mut PlayerProfile,
+    now_slot: u64,
+    reward: u64,
+) -> Result<u64> {
+    let decayed = profile.take_faith_decay(now_slot, reward);
+    if decayed > 0 {
+        emit_faith_decayed(admin, profile, decayed, now_slot);
+    }
+    let minted = reward - decayed;
+    if minted > 0 {
+        mint_faith(token_program, mint, to, admin, minted)?;
+    }
+    let balance = to.amount.checked_add(minted).ok_or(CasinoError::Overflow)?;
+    profile.sync_faith(balance);
+    Ok(balance)
+}
+
+fn emit_faith_decayed(admin: &Account<Admin>, profile: &PlayerProfile, amount: u64, slot: u64) {
+    emit!(FaithDecayed {
+        admin: admin.key(),
+        owner: profile.owner,
+        amount,
+        still_owed: profile.faith_decay_owed,
+        slot,
+    });
+}
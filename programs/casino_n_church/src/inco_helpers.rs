@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use inco_lightning::{
-    cpi::{self, accounts::Operation},
+    cpi::{self, accounts::{Allow, Operation, VerifySignature}},
     types::Euint128,
 };
 
+use crate::errors::CasinoError;
+
 /// Generate bounded random: 0 to (max-1)
 /// Example: generate_bounded_random(inco, signer, 37) -> 0-36 for roulette
 pub fn generate_bounded_random<'a>(
@@ -24,6 +26,83 @@ pub fn generate_bounded_random<'a>(
     cpi::e_rem(cpi_ctx, raw, max_val, 0)
 }
 
+/// A public value as an encrypted handle, to combine with encrypted operands
+pub fn as_encrypted<'a>(
+    inco: &AccountInfo<'a>,
+    signer: &AccountInfo<'a>,
+    value: u128,
+) -> Result<Euint128> {
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::as_euint128(cpi_ctx, value)
+}
+
+/// Grant `player` decrypt permission for each handle
+/// Expects 2 remaining accounts per handle (allowance PDA + player), in handle order;
+/// without them no permission is granted
+pub fn allow_handles<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    player: Pubkey,
+    handles: &[u128],
+) -> Result<()> {
+    if remaining_accounts.len() < 2 * handles.len() {
+        return Ok(());
+    }
+    
+    for (handle, accounts) in handles.iter().zip(remaining_accounts.chunks(2)) {
+        cpi::allow(
+            CpiContext::new(
+                inco.clone(),
+                Allow {
+                    allowance_account: accounts[0].clone(),
+                    signer: signer.clone(),
+                    allowed_address: accounts[1].clone(),
+                    system_program: system_program.clone(),
+                },
+            ),
+            *handle,
+            true,
+            player,
+        )?;
+    }
+    Ok(())
+}
+
+/// Verify an Inco-signed decryption of a game's payout handle and return the payout
+pub fn verify_decrypted_payout<'info>(
+    inco: AccountInfo<'info>,
+    instructions: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+    payout_handle: u128,
+    handle: Vec<u8>,
+    plaintext: Vec<u8>,
+) -> Result<u64> {
+    // The decrypted handle must be this game's payout, not some other handle
+    require!(
+        parse_handle_to_u128(&handle)? == payout_handle,
+        CasinoError::PayoutHandleMismatch
+    );
+    
+    // Verify decryption signature on-chain via Inco
+    cpi::is_validsignature(
+        CpiContext::new(inco, VerifySignature { instructions, signer }),
+        1, // Expected signature count
+        Some(vec![handle]),
+        Some(vec![plaintext.clone()]),
+    )?;
+    
+    parse_plaintext_to_u64(&plaintext)
+}
+
+/// Parse handle bytes (16-byte little-endian u128) back into the raw handle value
+pub fn parse_handle_to_u128(handle: &[u8]) -> Result<u128> {
+    let bytes: [u8; 16] = handle
+        .try_into()
+        .map_err(|_| error!(CasinoError::InvalidDecryptionProof))?;
+    Ok(u128::from_le_bytes(bytes))
+}
 
 /// Parse plaintext bytes to u64 (from Inco decryption result)
 pub fn parse_plaintext_to_u64(plaintext: &[u8]) -> Result<u64> {
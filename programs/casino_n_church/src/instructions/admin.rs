@@ -1,4 +1,12 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use crate::errors::CasinoError;
+use crate::events::{
+    AdminMigrated, ConfigUpdated, GameConfigUpdated, GuardianUpdated, MintConfigUpdated,
+    PauseUpdated, ProtocolFeeUpdated, TokenVaultWithdrawn, TreasuryWithdrawn, VaultConfigUpdated,
+    WithdrawDelayUpdated,
+};
+use crate::payout_math::BPS;
 use crate::states::*;
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
@@ -24,6 +32,14 @@ pub struct InitializeVault<'info> {
         bump
     )]
     pub admin: Account<'info, Admin>,
+    #[account(
+        init,
+        payer = house,
+        space = Exposure::DISCRIMINATOR.len() + Exposure::INIT_SPACE,
+        seeds = [b"exposure", admin.key().as_ref()],
+        bump
+    )]
+    pub exposure: Account<'info, Exposure>,
     pub system_program: Program<'info, System>,
 }
 
@@ -45,14 +61,96 @@ impl<'info> InitializeVault<'info> {
         };
         let lp_ctx = CpiContext::new(self.system_program.to_account_info(), lp_accounts);
         transfer(lp_ctx, amount)?;
-        self.admin.set_inner(Admin {
-            authority: self.house.key(),
-            treasury: self.house.key(),
-            casino_vault: self.casino_vault.key(),
-            lp_vault: self.lp_vault.key(),
-            house_edge_bps: 150,
-            quest_creation_fee: 100000000, // 0.1 SOL
-            bump: bumps.admin,
+        self.admin.set_inner(Admin::new(
+            self.house.key(),
+            self.casino_vault.key(),
+            self.lp_vault.key(),
+            bumps.admin,
+        ));
+        self.exposure.set_inner(Exposure {
+            admin: self.admin.key(),
+            outstanding: 0,
+            open_games: 0,
+            bump: bumps.exposure,
+        });
+        Ok(())
+    }
+}
+
+/// Upgrades an admin created by an earlier deployment (`AdminV0` layout) in place
+#[derive(Accounts)]
+pub struct MigrateAdmin<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: Still in the `AdminV0` layout, so it can't be loaded as `Admin`;
+    /// its discriminator, length and stored authority are checked in `migrate`
+    #[account(
+        mut,
+        seeds = [b"admin", authority.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub admin: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Exposure::DISCRIMINATOR.len() + Exposure::INIT_SPACE,
+        seeds = [b"exposure", admin.key().as_ref()],
+        bump
+    )]
+    pub exposure: Account<'info, Exposure>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAdmin<'info> {
+    /// Keep the legacy settings, default every newer field and grow the account to fit
+    pub fn migrate(&mut self, bumps: &MigrateAdminBumps) -> Result<()> {
+        let legacy = {
+            let data = self.admin.try_borrow_data()?;
+            require!(
+                data.len() == Admin::DISCRIMINATOR.len() + AdminV0::INIT_SPACE
+                    && data.starts_with(Admin::DISCRIMINATOR),
+                CasinoError::AlreadyMigrated
+            );
+            AdminV0::deserialize(&mut &data[Admin::DISCRIMINATOR.len()..])?
+        };
+        require_keys_eq!(legacy.authority, self.authority.key(), CasinoError::Unauthorized);
+
+        let space = Admin::DISCRIMINATOR.len() + Admin::INIT_SPACE;
+        let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(self.admin.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: self.admin.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        self.admin.resize(space)?;
+
+        let admin = Admin {
+            treasury: legacy.treasury,
+            house_edge_bps: legacy.house_edge_bps,
+            quest_creation_fee: legacy.quest_creation_fee,
+            ..Admin::new(legacy.authority, legacy.casino_vault, legacy.lp_vault, bumps.admin)
+        };
+        admin.try_serialize(&mut &mut self.admin.try_borrow_mut_data()?[..])?;
+
+        self.exposure.set_inner(Exposure {
+            admin: self.admin.key(),
+            outstanding: 0,
+            open_games: 0,
+            bump: bumps.exposure,
+        });
+
+        emit!(AdminMigrated {
+            admin: self.admin.key(),
+            authority: self.authority.key(),
+            slot: Clock::get()?.slot,
         });
         Ok(())
     }
@@ -64,18 +162,557 @@ pub struct UpdateConfig<'info> {
     #[account(
         mut,
         seeds = [b"admin", admin.authority.as_ref()],
-        bump = admin.bump
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
     )]
     pub admin: Account<'info, Admin>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub authority: Signer<'info>,
 }
 
 impl<'info> UpdateConfig<'info> {
-    pub fn update(&mut self, house_edge_bps: u16, quest_creation_fee: u64) -> Result<()> {
+    /// Set the quest creation fee and the defaults `init_game_config` copies into new games.
+    /// Games already configured keep their own limits; change those with `update_game_config`.
+    /// The default max bet may not exceed the casino_vault reserve target.
+    pub fn update(
+        &mut self,
+        house_edge_bps: u16,
+        quest_creation_fee: u64,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        require!(house_edge_bps <= MAX_HOUSE_EDGE_BPS, CasinoError::InvalidConfig);
+        require!(quest_creation_fee <= MAX_QUEST_CREATION_FEE, CasinoError::InvalidConfig);
+        require!(min_bet > 0 && min_bet <= max_bet, CasinoError::InvalidConfig);
+        require!(max_bet <= self.admin.reserve_target, CasinoError::InvalidConfig);
+
+        let old_house_edge_bps = self.admin.house_edge_bps;
+        let old_quest_creation_fee = self.admin.quest_creation_fee;
+
         self.admin.house_edge_bps = house_edge_bps;
         self.admin.quest_creation_fee = quest_creation_fee;
+        self.admin.min_bet = min_bet;
+        self.admin.max_bet = max_bet;
+
+        emit!(ConfigUpdated {
+            admin: self.admin.key(),
+            authority: self.authority.key(),
+            old_default_house_edge_bps: old_house_edge_bps,
+            new_default_house_edge_bps: house_edge_bps,
+            old_quest_creation_fee,
+            new_quest_creation_fee: quest_creation_fee,
+            default_min_bet: min_bet,
+            default_max_bet: max_bet,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+
+/// Tunable per-game settings passed to `update_game_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GameConfigParams {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub house_edge_bps: u16,
+    pub straight_multiplier: u64,
+    pub jackpot_multiplier: u64,
+    pub small_win_multiplier: u64,
+    pub max_multiplier_bps: u64,
+    pub paused: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u8)]
+pub struct InitGameConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        init,
+        payer = authority,
+        space = GameConfig::DISCRIMINATOR.len() + GameConfig::INIT_SPACE,
+        seeds = [b"game_config", admin.key().as_ref(), &[game_id]],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitGameConfig<'info> {
+    /// Create a game's config seeded from the global Admin limits and default multipliers
+    pub fn init(&mut self, game_id: u8, bumps: &InitGameConfigBumps) -> Result<()> {
+        let (straight_multiplier, jackpot_multiplier, small_win_multiplier, max_multiplier_bps) =
+            match game_id {
+                GAME_ID_ROULETTE => (DEFAULT_ROULETTE_STRAIGHT_MULTIPLIER, 0, 0, 0),
+                GAME_ID_COINFLIP => (0, 0, 0, 0),
+                GAME_ID_SLOT => (
+                    0,
+                    DEFAULT_SLOT_JACKPOT_MULTIPLIER,
+                    DEFAULT_SLOT_SMALL_WIN_MULTIPLIER,
+                    0,
+                ),
+                GAME_ID_AVIATOR => (0, 0, 0, DEFAULT_AVIATOR_MAX_MULTIPLIER_BPS),
+                _ => return err!(CasinoError::InvalidGameId),
+            };
+
+        self.game_config.set_inner(GameConfig {
+            admin: self.admin.key(),
+            game_id,
+            min_bet: self.admin.min_bet,
+            max_bet: self.admin.max_bet,
+            house_edge_bps: self.admin.house_edge_bps,
+            straight_multiplier,
+            jackpot_multiplier,
+            small_win_multiplier,
+            max_multiplier_bps,
+            paused: false,
+            bump: bumps.game_config,
+        });
+        self.game_config.validate(MAX_HOUSE_EDGE_BPS)
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateGameConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"game_config", admin.key().as_ref(), &[game_config.game_id]],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+}
+
+impl<'info> UpdateGameConfig<'info> {
+    pub fn update(&mut self, params: GameConfigParams) -> Result<()> {
+        let config = &mut self.game_config;
+        config.min_bet = params.min_bet;
+        config.max_bet = params.max_bet;
+        config.house_edge_bps = params.house_edge_bps;
+        config.straight_multiplier = params.straight_multiplier;
+        config.jackpot_multiplier = params.jackpot_multiplier;
+        config.small_win_multiplier = params.small_win_multiplier;
+        config.max_multiplier_bps = params.max_multiplier_bps;
+        config.paused = params.paused;
+        config.validate(MAX_HOUSE_EDGE_BPS)?;
+
+        emit!(GameConfigUpdated {
+            admin: config.admin,
+            game_id: config.game_id,
+            min_bet: config.min_bet,
+            max_bet: config.max_bet,
+            house_edge_bps: config.house_edge_bps,
+            straight_multiplier: config.straight_multiplier,
+            jackpot_multiplier: config.jackpot_multiplier,
+            small_win_multiplier: config.small_win_multiplier,
+            max_multiplier_bps: config.max_multiplier_bps,
+            paused: config.paused,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = admin.can_pause(&signer.key()) @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    /// Authority or guardian
+    pub signer: Signer<'info>,
+}
+
+impl<'info> SetPause<'info> {
+    pub fn set_pause(&mut self, paused: bool, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL_FLAGS == 0, CasinoError::InvalidConfig);
+
+        self.admin.paused = paused;
+        self.admin.pause_flags = pause_flags;
+
+        emit!(PauseUpdated {
+            admin: self.admin.key(),
+            signer: self.signer.key(),
+            paused,
+            pause_flags,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetGuardian<'info> {
+    pub fn set_guardian(&mut self, guardian: Pubkey) -> Result<()> {
+        let old_guardian = self.admin.guardian;
+        self.admin.guardian = guardian;
+
+        emit!(GuardianUpdated {
+            admin: self.admin.key(),
+            old_guardian,
+            new_guardian: guardian,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateVaultConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UpdateVaultConfig<'info> {
+    /// Set the casino_vault reserve target (e.g. a multiple of the largest possible
+    /// payout, and at least the default max bet), the treasury's share of profit swept by
+    /// `rebalance_vaults`, and the share of free bankroll a single bet's worst-case payout
+    /// may take
+    pub fn update(
+        &mut self,
+        reserve_target: u64,
+        treasury_share_bps: u16,
+        max_exposure_bps: u16,
+    ) -> Result<()> {
+        require!(
+            reserve_target >= self.admin.max_bet && reserve_target <= MAX_RESERVE_TARGET_LAMPORTS,
+            CasinoError::InvalidConfig
+        );
+        require!(treasury_share_bps <= MAX_TREASURY_SHARE_BPS, CasinoError::InvalidConfig);
+        require!(max_exposure_bps as u64 <= BPS, CasinoError::InvalidConfig);
+
+        self.admin.reserve_target = reserve_target;
+        self.admin.treasury_share_bps = treasury_share_bps;
+        self.admin.max_exposure_bps = max_exposure_bps;
+
+        emit!(VaultConfigUpdated {
+            admin: self.admin.key(),
+            reserve_target,
+            treasury_share_bps,
+            max_exposure_bps,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UpdateProtocolFee<'info> {
+    pub fn update(&mut self, protocol_fee_bps: u16) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, CasinoError::InvalidConfig);
+
+        let old_protocol_fee_bps = self.admin.protocol_fee_bps;
+        self.admin.protocol_fee_bps = protocol_fee_bps;
+
+        emit!(ProtocolFeeUpdated {
+            admin: self.admin.key(),
+            old_protocol_fee_bps,
+            new_protocol_fee_bps: protocol_fee_bps,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized,
+        has_one = treasury,
+        constraint = !admin.paused @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"casino_vault", admin.key().as_ref()],
+        bump
+    )]
+    pub casino_vault: SystemAccount<'info>,
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    /// Pay accrued protocol fees out of casino_vault to the treasury
+    pub fn withdraw(&mut self, amount: u64, bumps: &WithdrawTreasuryBumps) -> Result<()> {
+        require!(amount > 0, CasinoError::InvalidPayoutAmount);
+        require!(amount <= self.admin.treasury_accrued, CasinoError::InsufficientVaultFunds);
+
+        let admin_key = self.admin.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"casino_vault",
+            admin_key.as_ref(),
+            &[bumps.casino_vault],
+        ]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            Transfer {
+                from: self.casino_vault.to_account_info(),
+                to: self.treasury.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, amount)?;
+
+        self.admin.treasury_accrued -= amount;
+
+        emit!(TreasuryWithdrawn {
+            admin: admin_key,
+            treasury: self.treasury.key(),
+            amount,
+            remaining: self.admin.treasury_accrued,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateWithdrawDelay<'info> {
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", admin.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+}
+
+impl<'info> UpdateWithdrawDelay<'info> {
+    /// Applies to requests already queued as well as new ones
+    pub fn update(&mut self, delay_slots: u64) -> Result<()> {
+        require!(delay_slots <= MAX_WITHDRAW_DELAY_SLOTS, CasinoError::InvalidConfig);
+
+        let old_delay_slots = self.pool.withdraw_delay_slots;
+        self.pool.withdraw_delay_slots = delay_slots;
+
+        emit!(WithdrawDelayUpdated {
+            admin: self.admin.key(),
+            old_delay_slots,
+            new_delay_slots: delay_slots,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitMintConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = MintConfig::DISCRIMINATOR.len() + MintConfig::INIT_SPACE,
+        seeds = [b"mint_config", admin.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    /// Holds this mint's stakes and pays its winners; funded by plain token
+    /// transfers and emptied, down to its open liability, by `withdraw_token_vault`
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"token_vault", admin.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = mint_config
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitMintConfig<'info> {
+    /// Whitelist a mint for betting with its own limits (in the mint's base units)
+    pub fn init(&mut self, min_bet: u64, max_bet: u64, bumps: &InitMintConfigBumps) -> Result<()> {
+        self.mint_config.set_inner(MintConfig {
+            admin: self.admin.key(),
+            mint: self.mint.key(),
+            vault: self.token_vault.key(),
+            min_bet,
+            max_bet,
+            enabled: true,
+            outstanding: 0,
+            open_games: 0,
+            total_wagered: 0,
+            total_paid_out: 0,
+            bump: bumps.mint_config,
+            vault_bump: bumps.token_vault,
+        });
+        self.mint_config.validate()?;
+
+        emit!(MintConfigUpdated {
+            admin: self.admin.key(),
+            mint: self.mint.key(),
+            min_bet,
+            max_bet,
+            enabled: true,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"mint_config", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+}
+
+impl<'info> UpdateMintConfig<'info> {
+    /// Change a mint's limits, or disable it to stop new bets in that mint
+    pub fn update(&mut self, min_bet: u64, max_bet: u64, enabled: bool) -> Result<()> {
+        let config = &mut self.mint_config;
+        config.min_bet = min_bet;
+        config.max_bet = max_bet;
+        config.enabled = enabled;
+        config.validate()?;
+
+        emit!(MintConfigUpdated {
+            admin: config.admin,
+            mint: config.mint,
+            min_bet,
+            max_bet,
+            enabled,
+            slot: Clock::get()?.slot,
+        });
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTokenVault<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        seeds = [b"mint_config", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = mint_config.mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawTokenVault<'info> {
+    /// Move tokens out of a mint's vault, leaving enough to cover every open game's
+    /// worst-case payout in that mint
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, CasinoError::InvalidPayoutAmount);
+        let free = self.token_vault.amount.saturating_sub(self.mint_config.outstanding);
+        require!(amount <= free, CasinoError::LiabilityNotCovered);
+
+        let admin_key = self.admin.key();
+        let mint_key = self.mint_config.mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_config",
+            admin_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.mint_config.bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.token_vault.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.mint_config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TokenVaultWithdrawn {
+            admin: admin_key,
+            mint: mint_key,
+            destination: self.destination.key(),
+            amount,
+            remaining: self.token_vault.amount - amount,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
@@ -2,16 +2,14 @@ use anchor_lang::{
     prelude::*,
     system_program::{transfer, Transfer},
 };
-use inco_lightning::{
-    cpi::{self, accounts::VerifySignature},
-    program::IncoLightning,
-    ID as INCO_LIGHTNING_ID,
-};
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
 
 use crate::{
     errors::CasinoError,
-    inco_helpers::parse_plaintext_to_u64,
-    states::{Admin, GameResult},
+    inco_helpers::verify_decrypted_payout,
+    states::{
+        Admin, Exposure, GameResult, PlayerProfile, RouletteSpinLegs, LUCK_PER_WIN, PAUSE_CLAIMS,
+    },
 };
 
 // =============================================================================
@@ -24,8 +22,10 @@ pub struct ClaimRewards<'info> {
     pub player: Signer<'info>,
     
     #[account(
+        mut,
         seeds = [b"admin", admin.authority.as_ref()],
-        bump = admin.bump
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_CLAIMS) @ CasinoError::ProtocolPaused
     )]
     pub admin: Account<'info, Admin>,
     
@@ -43,13 +43,38 @@ pub struct ClaimRewards<'info> {
     )]
     pub lp_vault: SystemAccount<'info>,
     
+    #[account(mut, seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    
+    /// Closed on success, refunding its rent to the player
     #[account(
         mut,
+        close = player,
         constraint = game.player == player.key() @ CasinoError::Ed25519Pubkey,
+        constraint = game.admin == admin.key() @ CasinoError::Unauthorized,
+        constraint = game.mint == Pubkey::default() @ CasinoError::CurrencyMismatch,
+        constraint = !game.confidential @ CasinoError::CurrencyMismatch,
         constraint = !game.claimed @ CasinoError::AlreadyClaimed
     )]
     pub game: Account<'info, GameResult>,
     
+    /// Required for multi-bet roulette games; closed together with the game
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"roulette_legs", game.key().as_ref()],
+        bump = legs.bump
+    )]
+    pub legs: Option<Account<'info, RouletteSpinLegs>>,
+    
+    /// Created when the game was played; winning claims earn LUCK
+    #[account(
+        mut,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
     /// CHECK: Instructions sysvar for Ed25519 signature verification
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     pub instructions: AccountInfo<'info>,
@@ -66,25 +91,30 @@ impl<'info> ClaimRewards<'info> {
         handle: Vec<u8>,
         plaintext: Vec<u8>,
     ) -> Result<()> {
-        // Verify decryption signature on-chain via Inco
-        cpi::is_validsignature(
-            CpiContext::new(
-                ctx.accounts.inco_lightning_program.to_account_info(),
-                VerifySignature {
-                    instructions: ctx.accounts.instructions.to_account_info(),
-                    signer: ctx.accounts.player.to_account_info(),
-                },
-            ),
-            1, // Expected signature count
-            Some(vec![handle]),
-            Some(vec![plaintext.clone()]),
-        )?;
+        require!(
+            !ctx.accounts.game.has_roulette_legs() || ctx.accounts.legs.is_some(),
+            CasinoError::MissingRouletteLegs
+        );
         
-        // Parse verified payout amount
-        let payout = parse_plaintext_to_u64(&plaintext)?;
+        // Verify the decrypted payout on-chain via Inco
+        let payout = verify_decrypted_payout(
+            ctx.accounts.inco_lightning_program.to_account_info(),
+            ctx.accounts.instructions.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.game.payout_handle,
+            handle,
+            plaintext,
+        )?;
         
         if payout > 0 {
-            let casino_balance = ctx.accounts.casino_vault.lamports();
+            // The whole payout is a house loss, whichever vault funds it
+            ctx.accounts.admin.record_payout(payout)?;
+
+            // Accrued protocol fees stay in the vault for the treasury
+            let casino_balance = ctx
+                .accounts
+                .admin
+                .casino_float(ctx.accounts.casino_vault.lamports())?;
             
             if casino_balance >= payout {
                 // Full payout from casino vault
@@ -110,8 +140,13 @@ impl<'info> ClaimRewards<'info> {
             }
         }
         
-        // Mark as claimed
+        let luck = if payout > 0 { LUCK_PER_WIN } else { 0 };
+        ctx.accounts.profile.record_casino(Clock::get()?.slot, luck)?;
+        
+        // Mark as claimed; the account itself is closed to the player on exit
         ctx.accounts.game.claimed = true;
+        let max_payout = ctx.accounts.game.max_payout;
+        ctx.accounts.exposure.release(max_payout);
         
         msg!("Claimed {} lamports!", payout);
         Ok(())
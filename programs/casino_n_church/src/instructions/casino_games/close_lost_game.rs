@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::verify_decrypted_payout,
+    states::{Exposure, GameResult, MintConfig, RouletteSpinLegs},
+};
+
+// =============================================================================
+// CLOSE LOST GAME (rent refund for games with nothing to claim)
+// =============================================================================
+
+/// Decrypted payout handle and its Inco-signed plaintext
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PayoutProof {
+    pub handle: Vec<u8>,
+    pub plaintext: Vec<u8>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLostGame<'info> {
+    /// The player (forfeit or proof), anyone holding a zero-payout proof, or anyone at all
+    /// once the game has expired
+    #[account(mut)]
+    pub closer: Signer<'info>,
+
+    /// Rent always goes back to the player who paid it
+    #[account(mut, address = game.player @ CasinoError::Unauthorized)]
+    pub player: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        constraint = !game.claimed @ CasinoError::AlreadyClaimed,
+        // Confidential payouts are already set aside; claim_to_balance settles them
+        constraint = !game.confidential @ CasinoError::CurrencyMismatch
+    )]
+    pub game: Account<'info, GameResult>,
+
+    #[account(mut, seeds = [b"exposure", game.admin.as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+
+    /// Required for token games, whose liability is held against their mint instead
+    #[account(
+        mut,
+        seeds = [b"mint_config", game.admin.as_ref(), game.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
+    /// Required for multi-bet roulette games; closed together with the game
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"roulette_legs", game.key().as_ref()],
+        bump = legs.bump
+    )]
+    pub legs: Option<Account<'info, RouletteSpinLegs>>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> CloseLostGame<'info> {
+    /// Close a game that pays nothing and refund its rent to the player.
+    ///
+    /// With a proof, the payout must decrypt to zero and anyone may close it.
+    /// Without one, only the player may close it, forfeiting any winnings, until
+    /// `GAME_EXPIRY_SLOTS` have passed; after that anyone may, releasing its reservation.
+    pub fn close(ctx: Context<CloseLostGame>, proof: Option<PayoutProof>) -> Result<()> {
+        require!(
+            !ctx.accounts.game.has_roulette_legs() || ctx.accounts.legs.is_some(),
+            CasinoError::MissingRouletteLegs
+        );
+
+        match proof {
+            Some(PayoutProof { handle, plaintext }) => {
+                let payout = verify_decrypted_payout(
+                    ctx.accounts.inco_lightning_program.to_account_info(),
+                    ctx.accounts.instructions.to_account_info(),
+                    ctx.accounts.closer.to_account_info(),
+                    ctx.accounts.game.payout_handle,
+                    handle,
+                    plaintext,
+                )?;
+                require!(payout == 0, CasinoError::GameNotLost);
+                msg!("Closed lost game");
+            }
+            None if ctx.accounts.closer.key() == ctx.accounts.game.player => {
+                msg!("Game forfeited by player");
+            }
+            None => {
+                require!(
+                    ctx.accounts.game.is_expired(Clock::get()?.slot),
+                    CasinoError::Unauthorized
+                );
+                msg!("Expired game forfeited");
+            }
+        }
+
+        let max_payout = ctx.accounts.game.max_payout;
+        if ctx.accounts.game.mint == Pubkey::default() {
+            ctx.accounts.exposure.release(max_payout);
+        } else {
+            ctx.accounts
+                .mint_config
+                .as_mut()
+                .ok_or(CasinoError::CurrencyMismatch)?
+                .release(max_payout);
+        }
+
+        Ok(())
+    }
+}
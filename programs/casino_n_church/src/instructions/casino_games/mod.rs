@@ -5,6 +5,9 @@ pub use play_coinflip::*;
 pub mod play_roulette;
 pub use play_roulette::*;
 
+pub mod play_roulette_multi;
+pub use play_roulette_multi::*;
+
 pub mod play_slot;
 pub use play_slot::*;
 
@@ -13,3 +16,6 @@ pub use play_aviator::*;
 
 pub mod claim_rewards;
 pub use claim_rewards::*;
+
+pub mod close_lost_game;
+pub use close_lost_game::*;
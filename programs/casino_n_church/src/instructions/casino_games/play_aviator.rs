@@ -3,7 +3,7 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
+    cpi::{self, accounts::Operation},
     program::IncoLightning,
     types::{Euint128, Ebool},
     ID as INCO_LIGHTNING_ID,
@@ -11,28 +11,47 @@ use inco_lightning::{
 
 use crate::{
     errors::CasinoError,
-    inco_helpers::generate_bounded_random,
-    states::{GameResult, GameType},
+    inco_helpers::{allow_handles, as_encrypted, generate_bounded_random},
+    payout_math::{
+        aviator_stake_factor, calculate_aviator_payout, check_bet_limits, AVIATOR_CRASH_RANGE, BPS,
+    },
+    states::{
+        Admin, Boost, BoostTerms, Exposure, GameConfig, GameResult, GameType, PlayerProfile,
+        GAME_ID_AVIATOR, LUCK_PER_PLAY, MIN_AVIATOR_TARGET_BPS, PAUSE_GAMES,
+    },
 };
 
-const HOUSE_EDGE_BPS: u64 = 150;
-const BPS: u64 = 10_000;
-const MIN_BET_LAMPORTS: u64 = 10_000_000;
-const MAX_BET_LAMPORTS: u64 = 10_000_000_000;
-const MAX_MULTIPLIER_BPS: u32 = 100000; // 10x max
-
 #[derive(Accounts)]
 #[instruction(seed: u128)]
 pub struct PlayAviator<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
-    /// CHECK: Admin authority
-    pub admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_AVIATOR]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
     
     #[account(mut, seeds = [b"casino_vault", admin.key().as_ref()], bump)]
     pub casino_vault: SystemAccount<'info>,
     
+    /// LP capital also backs payouts, so it counts towards the bankroll
+    #[account(seeds = [b"lp_vault", admin.key().as_ref()], bump)]
+    pub lp_vault: SystemAccount<'info>,
+    
+    #[account(mut, seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    
     #[account(
         init,
         payer = player,
@@ -42,26 +61,62 @@ pub struct PlayAviator<'info> {
     )]
     pub game: Account<'info, GameResult>,
     
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    /// Voucher from `redeem_faith`, consumed and closed by this bet
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
+    
     #[account(address = INCO_LIGHTNING_ID)]
     pub inco_lightning_program: Program<'info, IncoLightning>,
     
     pub system_program: Program<'info, System>,
 }
 
-fn apply_house_edge(amount: u64) -> u64 {
-    let edge = amount.saturating_mul(HOUSE_EDGE_BPS) / BPS;
-    amount.saturating_sub(edge)
-}
-
 impl<'info> PlayAviator<'info> {
+    /// Player cashes out at an encrypted target multiplier if the plane has not crashed.
+    ///
+    /// `target_cap_bps` is a public upper bound on the target: it is validated against the
+    /// game config, recorded in `GameType`, and bounds the worst-case payout. The exact
+    /// target stays encrypted; a target outside [MIN_AVIATOR_TARGET_BPS, target_cap_bps]
+    /// pays nothing.
     pub fn play(
         ctx: Context<'_, '_, '_, 'info, PlayAviator<'info>>,
         seed: u128,
         encrypted_target_multiplier: Vec<u8>,
+        target_cap_bps: u32,
         amount: u64,
     ) -> Result<()> {
-        require!(amount >= MIN_BET_LAMPORTS, CasinoError::MinimumBet);
-        require!(amount <= MAX_BET_LAMPORTS, CasinoError::MaximumBet);
+        let config = &ctx.accounts.game_config;
+        check_bet_limits(amount, config.min_bet, config.max_bet)?;
+        let terms = BoostTerms::new(ctx.accounts.boost.as_deref(), amount, config.house_edge_bps)?;
+        let house_edge_bps = terms.house_edge_bps;
+        let payout_basis = terms.payout_basis(amount)?;
+        require!(
+            target_cap_bps as u64 >= MIN_AVIATOR_TARGET_BPS
+                && target_cap_bps as u64 <= config.max_multiplier_bps,
+            CasinoError::InvalidBetChoice
+        );
+        // Targets above the cap pay nothing, so the cap bounds the payout
+        let max_payout =
+            calculate_aviator_payout(payout_basis, target_cap_bps as u64, house_edge_bps)?;
+        
+        // A free bet's stake comes from the promo credit escrowed in its voucher
+        let casino_vault = ctx.accounts.casino_vault.to_account_info();
+        terms.release_promo_stake(&ctx.accounts.boost, &casino_vault)?;
         
         transfer(
             CpiContext::new(
@@ -71,88 +126,59 @@ impl<'info> PlayAviator<'info> {
                     to: ctx.accounts.casino_vault.to_account_info(),
                 },
             ),
-            amount,
+            terms.player_stake(amount),
         )?;
         
-        let inco = ctx.accounts.inco_lightning_program.to_account_info();
-        let signer = ctx.accounts.player.to_account_info();
-        
-        // Encrypt target multiplier
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let target: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_target_multiplier, 0)?;
-        
-        // Crash point: random in [10000, 100000] BPS (1.00x to 10.00x)
-        let crash_range = generate_bounded_random(inco.clone(), signer.clone(), 90000)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let base_crash = cpi::as_euint128(cpi_ctx, 10000u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let crash_point: Euint128 = cpi::e_add(cpi_ctx, crash_range, base_crash, 0)?;
-        
-        // Win if crash_point >= target
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let is_winner: Ebool = cpi::e_ge(cpi_ctx, crash_point, target, 0)?;
-        
-        // Max payout (simplified - actual would need FHE multiplication)
-        let max_payout = apply_house_edge(amount.saturating_mul(MAX_MULTIPLIER_BPS as u64) / BPS);
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let payout_enc = cpi::as_euint128(cpi_ctx, max_payout as u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+        ctx.accounts.admin.record_wager(amount, house_edge_bps)?;
         
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let payout = cpi::e_select(cpi_ctx, is_winner, payout_enc, zero, 0)?;
+        // Reject the bet if its worst case doesn't fit the bankroll left after open games
+        let bankroll = ctx.accounts.admin.bankroll(
+            ctx.accounts.casino_vault.lamports(),
+            ctx.accounts.lp_vault.lamports(),
+        )?;
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.exposure.reserve(max_payout, bankroll, max_exposure_bps)?;
         
-        // Log handles BEFORE allowance (so simulation can capture them)
-        msg!("Payout handle: {}", payout.0);
-        msg!("Crash point handle: {}", crash_point.0);
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let stake_factor =
+            as_encrypted(&inco, &signer, aviator_stake_factor(payout_basis, house_edge_bps)?)?;
+        let (target, crash_point, payout) = encrypted_aviator_flight(
+            &inco,
+            &signer,
+            encrypted_target_multiplier,
+            target_cap_bps,
+            config.max_multiplier_bps,
+            stake_factor,
+        )?;
         
         // Grant decrypt for BOTH payout and crash point
-        if ctx.remaining_accounts.len() >= 4 {
-            // Allow payout handle
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[0].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[1].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                payout.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-            
-            // Allow crash_point handle for verification
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[2].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[3].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                crash_point.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-        }
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[payout.0, crash_point.0],
+        )?;
+        
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
         
         ctx.accounts.game.set_inner(GameResult {
             player: ctx.accounts.player.key(),
-            game_type: GameType::Aviator { target_multiplier_bps: 0 },
+            admin: ctx.accounts.admin.key(),
+            mint: Pubkey::default(),
+            confidential: false,
+            game_type: GameType::Aviator { target_multiplier_bps: target_cap_bps },
             seed,
             bet_amount: amount,
-            slot: Clock::get()?.slot,
+            slot: now_slot,
             choice_handle: target.0,
             payout_handle: payout.0,
+            max_payout,
             random_handles: [crash_point.0, 0, 0, 0],  // Only 1 random result
             claimed: false,
             bump: ctx.bumps.game,
@@ -161,3 +187,88 @@ impl<'info> PlayAviator<'info> {
         Ok(())
     }
 }
+
+/// Fly to an encrypted crash point in [BPS, max_multiplier_bps] against an encrypted
+/// cashout target bounded by `target_cap_bps`. `stake_factor` is the encrypted
+/// `aviator_stake_factor` of the bet.
+/// Returns (target, crash_point, payout)
+pub(crate) fn encrypted_aviator_flight<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    encrypted_target_multiplier: Vec<u8>,
+    target_cap_bps: u32,
+    max_multiplier_bps: u64,
+    stake_factor: Euint128,
+) -> Result<(Euint128, Euint128, Euint128)> {
+    // Encrypt target multiplier
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let target: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_target_multiplier, 0)?;
+    
+    // Crash point: BPS * R / (R - r) for a uniform r in [0, R), capped at the configured
+    // ceiling, so it reaches any target T with probability ~BPS / T (see aviator_crash_point)
+    let draw = generate_bounded_random(inco.clone(), signer.clone(), AVIATOR_CRASH_RANGE as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let range = cpi::as_euint128(cpi_ctx, AVIATOR_CRASH_RANGE as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let remaining: Euint128 = cpi::e_sub(cpi_ctx, range, draw, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let scaled_range = cpi::as_euint128(cpi_ctx, BPS as u128 * AVIATOR_CRASH_RANGE as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let uncapped: Euint128 = cpi::e_div(cpi_ctx, scaled_range, remaining, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let ceiling = cpi::as_euint128(cpi_ctx, max_multiplier_bps as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let crash_point: Euint128 = cpi::e_min(cpi_ctx, uncapped, ceiling, 0)?;
+    
+    // Win if crash_point >= target
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let is_winner: Ebool = cpi::e_ge(cpi_ctx, crash_point, target, 0)?;
+    
+    // Target must lie within [MIN_AVIATOR_TARGET_BPS, target_cap_bps]
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let min_target = cpi::as_euint128(cpi_ctx, MIN_AVIATOR_TARGET_BPS as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let target_cap = cpi::as_euint128(cpi_ctx, target_cap_bps as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let above_min: Ebool = cpi::e_ge(cpi_ctx, target, min_target, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let below_cap: Ebool = cpi::e_le(cpi_ctx, target, target_cap, 0)?;
+    
+    // Win payout = amount * target * (BPS - edge) / BPS^2, computed on the encrypted target
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let scaled: Euint128 = cpi::e_mul(cpi_ctx, stake_factor, target, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let denominator = cpi::as_euint128(cpi_ctx, BPS as u128 * BPS as u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let win_payout: Euint128 = cpi::e_div(cpi_ctx, scaled, denominator, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+    
+    // payout = above_min ? (below_cap ? (is_winner ? win_payout : 0) : 0) : 0
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_winner = cpi::e_select(cpi_ctx, is_winner, win_payout, zero, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_below_cap = cpi::e_select(cpi_ctx, below_cap, if_winner, zero, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let payout = cpi::e_select(cpi_ctx, above_min, if_below_cap, zero, 0)?;
+    
+    // Log handles BEFORE allowance (so simulation can capture them)
+    msg!("Payout handle: {}", payout.0);
+    msg!("Crash point handle: {}", crash_point.0);
+    
+    Ok((target, crash_point, payout))
+}
@@ -3,7 +3,7 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
+    cpi::{self, accounts::Operation},
     program::IncoLightning,
     types::{Euint128, Ebool},
     ID as INCO_LIGHTNING_ID,
@@ -11,13 +11,15 @@ use inco_lightning::{
 
 use crate::{
     errors::CasinoError,
-    states::{GameResult, GameType},
+    inco_helpers::{allow_handles, as_encrypted},
+    payout_math::{calculate_win_payout, check_bet_limits},
+    states::{
+        Admin, Boost, BoostTerms, Exposure, GameConfig, GameResult, GameType, PlayerProfile,
+        GAME_ID_COINFLIP, LUCK_PER_PLAY, PAUSE_GAMES,
+    },
 };
 
-const HOUSE_EDGE_BPS: u64 = 150;
-const BPS: u64 = 10_000;
-const MIN_BET_LAMPORTS: u64 = 10_000_000; // 0.01 SOL
-const MAX_BET_LAMPORTS: u64 = 10_000_000_000; // 10 SOL
+pub(crate) const COINFLIP_MULTIPLIER: u64 = 2;
 
 // =============================================================================
 // PLAY COINFLIP (with Inco encryption)
@@ -29,8 +31,20 @@ pub struct PlayCoinflip<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
-    /// CHECK: Admin authority for vault PDA derivation
-    pub admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_COINFLIP]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
     
     #[account(
         mut,
@@ -39,6 +53,13 @@ pub struct PlayCoinflip<'info> {
     )]
     pub casino_vault: SystemAccount<'info>,
     
+    /// LP capital also backs payouts, so it counts towards the bankroll
+    #[account(seeds = [b"lp_vault", admin.key().as_ref()], bump)]
+    pub lp_vault: SystemAccount<'info>,
+    
+    #[account(mut, seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    
     #[account(
         init,
         payer = player,
@@ -48,6 +69,25 @@ pub struct PlayCoinflip<'info> {
     )]
     pub game: Account<'info, GameResult>,
     
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    /// Voucher from `redeem_faith`, consumed and closed by this bet
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
+    
     #[account(address = INCO_LIGHTNING_ID)]
     pub inco_lightning_program: Program<'info, IncoLightning>,
     
@@ -61,101 +101,74 @@ impl<'info> PlayCoinflip<'info> {
         encrypted_choice: Vec<u8>,
         amount: u64,
     ) -> Result<()> {
-        // Validate bet
-        require!(amount >= MIN_BET_LAMPORTS, CasinoError::MinimumBet);
-        require!(amount <= MAX_BET_LAMPORTS, CasinoError::MaximumBet);
+        // Validate bet against on-chain config
+        let config = &ctx.accounts.game_config;
+        check_bet_limits(amount, config.min_bet, config.max_bet)?;
+        let terms = BoostTerms::new(ctx.accounts.boost.as_deref(), amount, config.house_edge_bps)?;
+        let house_edge_bps = terms.house_edge_bps;
+        let payout_basis = terms.payout_basis(amount)?;
+        let win_payout = calculate_win_payout(payout_basis, COINFLIP_MULTIPLIER, house_edge_bps)?;
+        let max_payout = win_payout;
+        
+        // A free bet's stake comes from the promo credit escrowed in its voucher
+        let casino_vault = ctx.accounts.casino_vault.to_account_info();
+        terms.release_promo_stake(&ctx.accounts.boost, &casino_vault)?;
         
-        // Transfer bet to vault
+        // Transfer the rest of the bet to vault
         let transfer_accounts = Transfer {
             from: ctx.accounts.player.to_account_info(),
             to: ctx.accounts.casino_vault.to_account_info(),
         };
         transfer(
             CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts),
-            amount,
+            terms.player_stake(amount),
         )?;
         
-        let inco = ctx.accounts.inco_lightning_program.to_account_info();
-        let signer = ctx.accounts.player.to_account_info();
-        
-        // 1. Create encrypted choice from ciphertext (0 or 1)
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let choice: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_choice, 0)?;
+        ctx.accounts.admin.record_wager(amount, house_edge_bps)?;
         
-        // 2. Generate random 0-1
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let raw = cpi::e_rand(cpi_ctx, 0)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let two = cpi::as_euint128(cpi_ctx, 2u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let flip: Euint128 = cpi::e_rem(cpi_ctx, raw, two, 0)?;
-        
-        // 3. Compare: is_winner = (choice == flip)
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let is_winner: Ebool = cpi::e_eq(cpi_ctx, choice, flip, 0)?;
-        
-        // 4. Calculate encrypted payout using e_select
-        let win_payout = Self::calculate_win_payout(amount);
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let encrypted_payout = cpi::as_euint128(cpi_ctx, win_payout as u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let payout: Euint128 = cpi::e_select(cpi_ctx, is_winner, encrypted_payout, zero, 0)?;
+        // Reject the bet if its worst case doesn't fit the bankroll left after open games
+        let bankroll = ctx.accounts.admin.bankroll(
+            ctx.accounts.casino_vault.lamports(),
+            ctx.accounts.lp_vault.lamports(),
+        )?;
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.exposure.reserve(max_payout, bankroll, max_exposure_bps)?;
         
-        // Log handles BEFORE allowance (so simulation can capture them)
-        msg!("Payout handle: {}", payout.0);
-        msg!("Random handle: {}", flip.0);
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let win_payout = as_encrypted(&inco, &signer, win_payout as u128)?;
+        let (choice, flip, payout) =
+            encrypted_coinflip(&inco, &signer, encrypted_choice, win_payout)?;
         
-        // 5. Grant player permission to decrypt BOTH payout and flip result
+        // Grant player permission to decrypt BOTH payout and flip result
         // Requires 4 remaining accounts: 2 per handle (allowance PDA + player)
-        if ctx.remaining_accounts.len() >= 4 {
-            // Allow payout handle (accounts 0-1)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[0].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[1].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                payout.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-            
-            // Allow flip result handle (accounts 2-3)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[2].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[3].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                flip.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-        }
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[payout.0, flip.0],
+        )?;
+        
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
         
         // Store game result with encrypted handles
         ctx.accounts.game.set_inner(GameResult {
             player: ctx.accounts.player.key(),
+            admin: ctx.accounts.admin.key(),
+            mint: Pubkey::default(),
+            confidential: false,
             game_type: GameType::Coinflip,
             seed,
             bet_amount: amount,
-            slot: Clock::get()?.slot,
+            slot: now_slot,
             choice_handle: choice.0,
             payout_handle: payout.0,
+            max_payout,
             random_handles: [flip.0, 0, 0, 0],
             claimed: false,
             bump: ctx.bumps.game,
@@ -163,11 +176,44 @@ impl<'info> PlayCoinflip<'info> {
         
         Ok(())
     }
+}
+
+/// Encrypted coinflip of `encrypted_choice` (0 or 1) against a random flip
+/// Returns (choice, flip, payout), the payout being win_payout on a match and 0 otherwise
+pub(crate) fn encrypted_coinflip<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    encrypted_choice: Vec<u8>,
+    win_payout: Euint128,
+) -> Result<(Euint128, Euint128, Euint128)> {
+    // 1. Create encrypted choice from ciphertext (0 or 1)
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let choice: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_choice, 0)?;
     
-    /// Calculate win payout: 2x bet minus house edge
-    fn calculate_win_payout(amount: u64) -> u64 {
-        let gross = amount.saturating_mul(2);
-        let edge = gross.saturating_mul(HOUSE_EDGE_BPS) / BPS;
-        gross.saturating_sub(edge)
-    }
+    // 2. Generate random 0-1
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let raw = cpi::e_rand(cpi_ctx, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let two = cpi::as_euint128(cpi_ctx, 2u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let flip: Euint128 = cpi::e_rem(cpi_ctx, raw, two, 0)?;
+    
+    // 3. Compare: is_winner = (choice == flip)
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let is_winner: Ebool = cpi::e_eq(cpi_ctx, choice, flip, 0)?;
+    
+    // 4. Select the encrypted payout (2x bet minus house edge) on a win
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let payout: Euint128 = cpi::e_select(cpi_ctx, is_winner, win_payout, zero, 0)?;
+    
+    // Log handles BEFORE allowance (so simulation can capture them)
+    msg!("Payout handle: {}", payout.0);
+    msg!("Random handle: {}", flip.0);
+    
+    Ok((choice, flip, payout))
 }
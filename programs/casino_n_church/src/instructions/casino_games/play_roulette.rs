@@ -3,7 +3,7 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
+    cpi::{self, accounts::Operation},
     program::IncoLightning,
     types::{Euint128, Ebool},
     ID as INCO_LIGHTNING_ID,
@@ -11,29 +11,47 @@ use inco_lightning::{
 
 use crate::{
     errors::CasinoError,
-    inco_helpers::generate_bounded_random,
-    states::{GameResult, GameType},
+    inco_helpers::{allow_handles, as_encrypted, generate_bounded_random},
+    payout_math::{calculate_win_payout, check_bet_limits, roulette_multiplier},
+    states::{
+        Admin, Boost, BoostTerms, Exposure, GameConfig, GameResult, GameType, PlayerProfile,
+        GAME_ID_ROULETTE, LUCK_PER_PLAY, PAUSE_GAMES, ROULETTE_BET_COLOR, ROULETTE_BET_COLUMN,
+        ROULETTE_BET_DOZEN, ROULETTE_BET_HIGH_LOW, ROULETTE_BET_ODD_EVEN, ROULETTE_BET_SPLIT,
+        ROULETTE_BET_STRAIGHT,
+    },
 };
 
-const HOUSE_EDGE_BPS: u64 = 150;
-const BPS: u64 = 10_000;
-const MIN_BET_LAMPORTS: u64 = 10_000_000;
-const MAX_BET_LAMPORTS: u64 = 10_000_000_000;
-
-const STRAIGHT_BET_MULTIPLIER: u64 = 36;  // 35:1 + original bet
-
 #[derive(Accounts)]
 #[instruction(seed: u128)]
 pub struct PlayRoulette<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
-    /// CHECK: Admin authority
-    pub admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_ROULETTE]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
     
     #[account(mut, seeds = [b"casino_vault", admin.key().as_ref()], bump)]
     pub casino_vault: SystemAccount<'info>,
     
+    /// LP capital also backs payouts, so it counts towards the bankroll
+    #[account(seeds = [b"lp_vault", admin.key().as_ref()], bump)]
+    pub lp_vault: SystemAccount<'info>,
+    
+    #[account(mut, seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    
     #[account(
         init,
         payer = player,
@@ -43,32 +61,59 @@ pub struct PlayRoulette<'info> {
     )]
     pub game: Account<'info, GameResult>,
     
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    /// Voucher from `redeem_faith`, consumed and closed by this bet
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
+    
     #[account(address = INCO_LIGHTNING_ID)]
     pub inco_lightning_program: Program<'info, IncoLightning>,
     
     pub system_program: Program<'info, System>,
 }
 
-fn apply_house_edge(amount: u64) -> u64 {
-    let edge = amount.saturating_mul(HOUSE_EDGE_BPS) / BPS;
-    amount.saturating_sub(edge)
-}
-
 impl<'info> PlayRoulette<'info> {
-    /// Simplified Roulette: Straight bet only (exact number 0-36)
+    /// Roulette with a public bet type and an encrypted choice (see ROULETTE_BET_*)
     /// 
-    /// Player bets on a specific number. If spin matches, wins 35:1.
-    /// Similar to coinflip pattern but with 37 outcomes.
+    /// Straight bets pick a number 0-36 and pay 35:1; split bets cover two numbers
+    /// (first * 37 + second) and pay 17:1. Outside bets (color, odd/even,
+    /// high/low, dozens, columns) compare the choice against an encrypted category of
+    /// the spin and lose on zero. Out-of-range choices simply never match.
     pub fn play(
         ctx: Context<'_, '_, '_, 'info, PlayRoulette<'info>>,
         seed: u128,
-        encrypted_choice: Vec<u8>,  // Number 0-36 (encrypted)
+        bet_type: u8,
+        encrypted_choice: Vec<u8>,  // Interpreted per bet_type (encrypted)
         amount: u64,
     ) -> Result<()> {
-        require!(amount >= MIN_BET_LAMPORTS, CasinoError::MinimumBet);
-        require!(amount <= MAX_BET_LAMPORTS, CasinoError::MaximumBet);
+        let config = &ctx.accounts.game_config;
+        check_bet_limits(amount, config.min_bet, config.max_bet)?;
+        let terms = BoostTerms::new(ctx.accounts.boost.as_deref(), amount, config.house_edge_bps)?;
+        let house_edge_bps = terms.house_edge_bps;
+        let payout_basis = terms.payout_basis(amount)?;
+        let multiplier = roulette_multiplier(bet_type, config.straight_multiplier)?;
+        let win_payout = calculate_win_payout(payout_basis, multiplier, house_edge_bps)?;
+        let max_payout = win_payout;
+        
+        // A free bet's stake comes from the promo credit escrowed in its voucher
+        let casino_vault = ctx.accounts.casino_vault.to_account_info();
+        terms.release_promo_stake(&ctx.accounts.boost, &casino_vault)?;
         
-        // Transfer bet
+        // Transfer the rest of the bet
         transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
@@ -77,84 +122,52 @@ impl<'info> PlayRoulette<'info> {
                     to: ctx.accounts.casino_vault.to_account_info(),
                 },
             ),
-            amount,
+            terms.player_stake(amount),
         )?;
         
-        let inco = ctx.accounts.inco_lightning_program.to_account_info();
-        let signer = ctx.accounts.player.to_account_info();
-        
-        // Encrypt player's choice (0-36)
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let choice: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_choice, 0)?;
-        
-        // Spin wheel (0-36)
-        let spin: Euint128 = generate_bounded_random(inco.clone(), signer.clone(), 37)?;
-        
-        // Log spin handle early to avoid truncation
-        msg!("Spin handle: {}", spin.0);
-        
-        // Check if player won: is_winner = (choice == spin)
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let is_winner: Ebool = cpi::e_eq(cpi_ctx, choice, spin, 0)?;
-        
-        // Calculate payout: winner gets 35:1
-        let win_payout = apply_house_edge(amount.saturating_mul(STRAIGHT_BET_MULTIPLIER));
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let encrypted_payout = cpi::as_euint128(cpi_ctx, win_payout as u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+        ctx.accounts.admin.record_wager(amount, house_edge_bps)?;
         
-        // payout = is_winner ? win_payout : 0
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let payout: Euint128 = cpi::e_select(cpi_ctx, is_winner, encrypted_payout, zero, 0)?;
+        // Reject the bet if its worst case doesn't fit the bankroll left after open games
+        let bankroll = ctx.accounts.admin.bankroll(
+            ctx.accounts.casino_vault.lamports(),
+            ctx.accounts.lp_vault.lamports(),
+        )?;
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.exposure.reserve(max_payout, bankroll, max_exposure_bps)?;
         
-        // Log payout handle
-        msg!("Payout handle: {}", payout.0);
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let win_payout = as_encrypted(&inco, &signer, win_payout as u128)?;
+        let (choice, spin, payout) =
+            encrypted_roulette_spin(&inco, &signer, bet_type, encrypted_choice, win_payout)?;
         
         // Grant decrypt permission for BOTH payout and spin
-        if ctx.remaining_accounts.len() >= 4 {
-            // Allow payout handle (accounts 0-1)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[0].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[1].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                payout.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-            
-            // Allow spin handle (accounts 2-3)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[2].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[3].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                spin.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-        }
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[payout.0, spin.0],
+        )?;
+        
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
         
         ctx.accounts.game.set_inner(GameResult {
             player: ctx.accounts.player.key(),
-            game_type: GameType::Roulette { bet_type: 0 },  // Straight bet
+            admin: ctx.accounts.admin.key(),
+            mint: Pubkey::default(),
+            confidential: false,
+            game_type: GameType::Roulette { bet_type },
             seed,
             bet_amount: amount,
-            slot: Clock::get()?.slot,
+            slot: now_slot,
             choice_handle: choice.0,
             payout_handle: payout.0,
+            max_payout,
             random_handles: [spin.0, 0, 0, 0],
             claimed: false,
             bump: ctx.bumps.game,
@@ -163,3 +176,199 @@ impl<'info> PlayRoulette<'info> {
         Ok(())
     }
 }
+
+/// Encrypted payout of a single roulette bet against the spin: win_payout if the
+/// choice matches the spin's category for `bet_type`, else 0 (outside bets lose on 0)
+pub(crate) fn encrypted_bet_payout<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    bet_type: u8,
+    choice: Euint128,
+    spin: Euint128,
+    win_payout: Euint128,
+) -> Result<Euint128> {
+    if bet_type == ROULETTE_BET_SPLIT {
+        return encrypted_split_payout(inco, signer, choice, spin, win_payout);
+    }
+    
+    let category = encrypted_spin_category(inco, signer, bet_type, spin)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let is_match: Ebool = cpi::e_eq(cpi_ctx, choice, category, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let payout = cpi::e_select(cpi_ctx, is_match, win_payout, zero, 0)?;
+    
+    if bet_type == ROULETTE_BET_STRAIGHT {
+        return Ok(payout);
+    }
+    
+    // Zero is green: every outside bet loses
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let one = cpi::as_euint128(cpi_ctx, 1u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let is_nonzero: Ebool = cpi::e_ge(cpi_ctx, spin, one, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_select(cpi_ctx, is_nonzero, payout, zero, 0)
+}
+
+/// Split bet: choice encodes first * 37 + second; pays if either number hits
+fn encrypted_split_payout<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    choice: Euint128,
+    spin: Euint128,
+    win_payout: Euint128,
+) -> Result<Euint128> {
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let pockets = cpi::as_euint128(cpi_ctx, 37u128)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let first = cpi::e_div(cpi_ctx, choice, pockets, 0)?;
+    
+    let second = encrypted_rem(inco, signer, choice, 37)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let hit_first: Ebool = cpi::e_eq(cpi_ctx, first, spin, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let hit_second: Ebool = cpi::e_eq(cpi_ctx, second, spin, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+    
+    // hit_first ? win : (hit_second ? win : 0)
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_second = cpi::e_select(cpi_ctx, hit_second, win_payout, zero, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_select(cpi_ctx, hit_first, win_payout, if_second, 0)
+}
+
+/// Encrypted category of the spin for `bet_type` (mirrors `payout_math::roulette_category`)
+fn encrypted_spin_category<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    bet_type: u8,
+    spin: Euint128,
+) -> Result<Euint128> {
+    match bet_type {
+        ROULETTE_BET_STRAIGHT => Ok(spin),
+        ROULETTE_BET_COLOR => {
+            // Red is odd in 1-10 / 19-28 and even in 11-18 / 29-36:
+            // flipped = (spin + 17) % 18 >= 10, color = flipped ? parity : 1 - parity
+            let parity = encrypted_rem(inco, signer, spin, 2)?;
+            let shifted = encrypted_add(inco, signer, spin, 17)?;
+            let block = encrypted_rem(inco, signer, shifted, 18)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let ten = cpi::as_euint128(cpi_ctx, 10u128)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let flipped: Ebool = cpi::e_ge(cpi_ctx, block, ten, 0)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let one = cpi::as_euint128(cpi_ctx, 1u128)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let inverted = cpi::e_sub(cpi_ctx, one, parity, 0)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            cpi::e_select(cpi_ctx, flipped, parity, inverted, 0)
+        }
+        ROULETTE_BET_ODD_EVEN => encrypted_rem(inco, signer, spin, 2),
+        ROULETTE_BET_HIGH_LOW => {
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let nineteen = cpi::as_euint128(cpi_ctx, 19u128)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let is_high: Ebool = cpi::e_ge(cpi_ctx, spin, nineteen, 0)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let one = cpi::as_euint128(cpi_ctx, 1u128)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            cpi::e_select(cpi_ctx, is_high, one, zero, 0)
+        }
+        ROULETTE_BET_DOZEN => {
+            // (spin + 35) / 12 % 3 maps 1-12 -> 0, 13-24 -> 1, 25-36 -> 2
+            let shifted = encrypted_add(inco, signer, spin, 35)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let twelve = cpi::as_euint128(cpi_ctx, 12u128)?;
+            
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let dozen = cpi::e_div(cpi_ctx, shifted, twelve, 0)?;
+            
+            encrypted_rem(inco, signer, dozen, 3)
+        }
+        ROULETTE_BET_COLUMN => {
+            // (spin + 2) % 3 maps 1,4.. -> 0, 2,5.. -> 1, 3,6.. -> 2
+            let shifted = encrypted_add(inco, signer, spin, 2)?;
+            encrypted_rem(inco, signer, shifted, 3)
+        }
+        _ => err!(CasinoError::InvalidBetType),
+    }
+}
+
+fn encrypted_add<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    value: Euint128,
+    rhs: u128,
+) -> Result<Euint128> {
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let rhs = cpi::as_euint128(cpi_ctx, rhs)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_add(cpi_ctx, value, rhs, 0)
+}
+
+fn encrypted_rem<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    value: Euint128,
+    modulus: u128,
+) -> Result<Euint128> {
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let modulus = cpi::as_euint128(cpi_ctx, modulus)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_rem(cpi_ctx, value, modulus, 0)
+}
+
+/// Spin the wheel (0-36) against an encrypted choice for `bet_type`
+/// Returns (choice, spin, payout)
+pub(crate) fn encrypted_roulette_spin<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    bet_type: u8,
+    encrypted_choice: Vec<u8>,
+    win_payout: Euint128,
+) -> Result<(Euint128, Euint128, Euint128)> {
+    // Encrypt player's choice
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let choice: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_choice, 0)?;
+    
+    // Spin wheel (0-36)
+    let spin: Euint128 = generate_bounded_random(inco.clone(), signer.clone(), 37)?;
+    
+    // Log spin handle early to avoid truncation
+    msg!("Spin handle: {}", spin.0);
+    
+    // payout = choice matches the spin for this bet type ? win_payout : 0
+    let payout = encrypted_bet_payout(inco, signer, bet_type, choice, spin, win_payout)?;
+    
+    // Log payout handle
+    msg!("Payout handle: {}", payout.0);
+    
+    Ok((choice, spin, payout))
+}
@@ -0,0 +1,277 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use inco_lightning::{
+    cpi::{self, accounts::{Allow, Operation}},
+    program::IncoLightning,
+    types::Euint128,
+    ID as INCO_LIGHTNING_ID,
+};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::generate_bounded_random,
+    instructions::casino_games::play_roulette::encrypted_bet_payout,
+    payout_math::{calculate_win_payout, roulette_multiplier},
+    states::{
+        Admin, Boost, BoostTerms, Exposure, GameConfig, GameResult, GameType, PlayerProfile,
+        RouletteLeg, RouletteSpinLegs, GAME_ID_ROULETTE, LUCK_PER_PLAY, MAX_ROULETTE_LEGS,
+        PAUSE_GAMES, ROULETTE_BET_MULTI,
+    },
+};
+
+/// One wager submitted to `play_roulette_multi`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RouletteLegInput {
+    /// One of the ROULETTE_BET_* constants
+    pub bet_type: u8,
+    /// Choice interpreted per bet_type (encrypted)
+    pub encrypted_choice: Vec<u8>,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: u128)]
+pub struct PlayRouletteMulti<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_ROULETTE]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(mut, seeds = [b"casino_vault", admin.key().as_ref()], bump)]
+    pub casino_vault: SystemAccount<'info>,
+
+    /// LP capital also backs payouts, so it counts towards the bankroll
+    #[account(seeds = [b"lp_vault", admin.key().as_ref()], bump)]
+    pub lp_vault: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameResult::INIT_SPACE,
+        seeds = [b"game_roulette", player.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: Account<'info, GameResult>,
+
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    /// Voucher from `redeem_faith`, consumed and closed by this bet
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + RouletteSpinLegs::INIT_SPACE,
+        seeds = [b"roulette_legs", game.key().as_ref()],
+        bump
+    )]
+    pub legs: Account<'info, RouletteSpinLegs>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlayRouletteMulti<'info> {
+    /// Several encrypted wagers on one spin, like a real table.
+    ///
+    /// Each leg is paid exactly as a single `play_roulette` bet would be; the leg
+    /// payouts are summed homomorphically into the game's single payout handle.
+    /// Every leg must meet the minimum bet and the total must stay within the maximum.
+    pub fn play(
+        ctx: Context<'_, '_, '_, 'info, PlayRouletteMulti<'info>>,
+        seed: u128,
+        legs: Vec<RouletteLegInput>,
+    ) -> Result<()> {
+        require!(
+            !legs.is_empty() && legs.len() <= MAX_ROULETTE_LEGS,
+            CasinoError::InvalidBetType
+        );
+
+        let config = &ctx.accounts.game_config;
+
+        let mut total_amount: u64 = 0;
+        for leg in &legs {
+            require!(leg.amount >= config.min_bet, CasinoError::MinimumBet);
+            total_amount = total_amount.checked_add(leg.amount).ok_or(CasinoError::Overflow)?;
+        }
+        require!(total_amount <= config.max_bet, CasinoError::MaximumBet);
+
+        // A voucher applies to the whole spin; a free bet covers the combined stake
+        let terms =
+            BoostTerms::new(ctx.accounts.boost.as_deref(), total_amount, config.house_edge_bps)?;
+        let house_edge_bps = terms.house_edge_bps;
+        let mut win_payouts = Vec::with_capacity(legs.len());
+        for leg in &legs {
+            let multiplier = roulette_multiplier(leg.bet_type, config.straight_multiplier)?;
+            let payout_basis = terms.payout_basis(leg.amount)?;
+            win_payouts.push(calculate_win_payout(payout_basis, multiplier, house_edge_bps)?);
+        }
+        // Bounded as if every leg won, though no spin can satisfy some combinations
+        let max_payout = win_payouts
+            .iter()
+            .try_fold(0u64, |sum, payout| sum.checked_add(*payout))
+            .ok_or(CasinoError::Overflow)?;
+
+        // A free bet's stake comes from the promo credit escrowed in its voucher
+        let casino_vault = ctx.accounts.casino_vault.to_account_info();
+        terms.release_promo_stake(&ctx.accounts.boost, &casino_vault)?;
+
+        // Transfer the rest of the combined stake
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.casino_vault.to_account_info(),
+                },
+            ),
+            terms.player_stake(total_amount),
+        )?;
+
+        ctx.accounts.admin.record_wager(total_amount, house_edge_bps)?;
+
+        // Reject the bet if its worst case doesn't fit the bankroll left after open games
+        let bankroll = ctx.accounts.admin.bankroll(
+            ctx.accounts.casino_vault.lamports(),
+            ctx.accounts.lp_vault.lamports(),
+        )?;
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.exposure.reserve(max_payout, bankroll, max_exposure_bps)?;
+
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+
+        // One spin for every leg (0-36)
+        let spin: Euint128 = generate_bounded_random(inco.clone(), signer.clone(), 37)?;
+
+        // Log spin handle early to avoid truncation
+        msg!("Spin handle: {}", spin.0);
+
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let mut payout = cpi::as_euint128(cpi_ctx, 0u128)?;
+
+        let mut recorded_legs = Vec::with_capacity(legs.len());
+        for (leg, win_payout) in legs.into_iter().zip(win_payouts) {
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let choice: Euint128 = cpi::new_euint128(cpi_ctx, leg.encrypted_choice, 0)?;
+
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            let win_payout = cpi::as_euint128(cpi_ctx, win_payout as u128)?;
+
+            let leg_payout =
+                encrypted_bet_payout(&inco, &signer, leg.bet_type, choice, spin, win_payout)?;
+
+            let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+            payout = cpi::e_add(cpi_ctx, payout, leg_payout, 0)?;
+
+            recorded_legs.push(RouletteLeg {
+                bet_type: leg.bet_type,
+                amount: leg.amount,
+                choice_handle: choice.0,
+                payout_handle: leg_payout.0,
+            });
+        }
+
+        // Log payout handle
+        msg!("Payout handle: {}", payout.0);
+
+        // Grant decrypt permission for BOTH total payout and spin
+        if ctx.remaining_accounts.len() >= 4 {
+            // Allow payout handle (accounts 0-1)
+            cpi::allow(
+                CpiContext::new(
+                    inco.clone(),
+                    Allow {
+                        allowance_account: ctx.remaining_accounts[0].clone(),
+                        signer: signer.clone(),
+                        allowed_address: ctx.remaining_accounts[1].clone(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                ),
+                payout.0,
+                true,
+                ctx.accounts.player.key(),
+            )?;
+
+            // Allow spin handle (accounts 2-3)
+            cpi::allow(
+                CpiContext::new(
+                    inco.clone(),
+                    Allow {
+                        allowance_account: ctx.remaining_accounts[2].clone(),
+                        signer: signer.clone(),
+                        allowed_address: ctx.remaining_accounts[3].clone(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                ),
+                spin.0,
+                true,
+                ctx.accounts.player.key(),
+            )?;
+        }
+
+        ctx.accounts.legs.set_inner(RouletteSpinLegs {
+            game: ctx.accounts.game.key(),
+            legs: recorded_legs,
+            bump: ctx.bumps.legs,
+        });
+
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
+
+        ctx.accounts.game.set_inner(GameResult {
+            player: ctx.accounts.player.key(),
+            admin: ctx.accounts.admin.key(),
+            mint: Pubkey::default(),
+            confidential: false,
+            game_type: GameType::Roulette { bet_type: ROULETTE_BET_MULTI },
+            seed,
+            bet_amount: total_amount,
+            slot: now_slot,
+            choice_handle: 0,  // Per-leg choices live in RouletteSpinLegs
+            payout_handle: payout.0,
+            max_payout,
+            random_handles: [spin.0, 0, 0, 0],
+            claimed: false,
+            bump: ctx.bumps.game,
+        });
+
+        Ok(())
+    }
+}
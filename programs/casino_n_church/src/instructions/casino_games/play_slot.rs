@@ -3,7 +3,7 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
+    cpi::{self, accounts::Operation},
     program::IncoLightning,
     types::{Euint128, Ebool},
     ID as INCO_LIGHTNING_ID,
@@ -11,30 +11,45 @@ use inco_lightning::{
 
 use crate::{
     errors::CasinoError,
-    inco_helpers::generate_bounded_random,
-    states::{GameResult, GameType},
+    inco_helpers::{allow_handles, as_encrypted, generate_bounded_random},
+    payout_math::{calculate_win_payout, check_bet_limits, SLOT_REEL_SYMBOLS},
+    states::{
+        Admin, Boost, BoostTerms, Exposure, GameConfig, GameResult, GameType, PlayerProfile,
+        GAME_ID_SLOT, LUCK_PER_PLAY, PAUSE_GAMES,
+    },
 };
 
-const HOUSE_EDGE_BPS: u64 = 150;
-const BPS: u64 = 10_000;
-const MIN_BET_LAMPORTS: u64 = 10_000_000;
-const MAX_BET_LAMPORTS: u64 = 10_000_000_000;
-
-const JACKPOT_MULTIPLIER: u64 = 50;
-const SMALL_WIN_MULTIPLIER: u64 = 5;
-
 #[derive(Accounts)]
 #[instruction(seed: u128)]
 pub struct PlaySlot<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
-    /// CHECK: Admin authority
-    pub admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_SLOT]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
     
     #[account(mut, seeds = [b"casino_vault", admin.key().as_ref()], bump)]
     pub casino_vault: SystemAccount<'info>,
     
+    /// LP capital also backs payouts, so it counts towards the bankroll
+    #[account(seeds = [b"lp_vault", admin.key().as_ref()], bump)]
+    pub lp_vault: SystemAccount<'info>,
+    
+    #[account(mut, seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    
     #[account(
         init,
         payer = player,
@@ -44,25 +59,53 @@ pub struct PlaySlot<'info> {
     )]
     pub game: Account<'info, GameResult>,
     
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    /// Voucher from `redeem_faith`, consumed and closed by this bet
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
+    
     #[account(address = INCO_LIGHTNING_ID)]
     pub inco_lightning_program: Program<'info, IncoLightning>,
     
     pub system_program: Program<'info, System>,
 }
 
-fn apply_house_edge(amount: u64) -> u64 {
-    let edge = amount.saturating_mul(HOUSE_EDGE_BPS) / BPS;
-    amount.saturating_sub(edge)
-}
-
 impl<'info> PlaySlot<'info> {
     pub fn play(
         ctx: Context<'_, '_, '_, 'info, PlaySlot<'info>>,
         seed: u128,
         amount: u64,
     ) -> Result<()> {
-        require!(amount >= MIN_BET_LAMPORTS, CasinoError::MinimumBet);
-        require!(amount <= MAX_BET_LAMPORTS, CasinoError::MaximumBet);
+        let config = &ctx.accounts.game_config;
+        check_bet_limits(amount, config.min_bet, config.max_bet)?;
+        let terms = BoostTerms::new(ctx.accounts.boost.as_deref(), amount, config.house_edge_bps)?;
+        let house_edge_bps = terms.house_edge_bps;
+        let payout_basis = terms.payout_basis(amount)?;
+        
+        // Payout amounts (config validation keeps the jackpot the largest)
+        let jackpot_amount =
+            calculate_win_payout(payout_basis, config.jackpot_multiplier, house_edge_bps)?;
+        let small_amount =
+            calculate_win_payout(payout_basis, config.small_win_multiplier, house_edge_bps)?;
+        let max_payout = jackpot_amount;
+        
+        // A free bet's stake comes from the promo credit escrowed in its voucher
+        let casino_vault = ctx.accounts.casino_vault.to_account_info();
+        terms.release_promo_stake(&ctx.accounts.boost, &casino_vault)?;
         
         transfer(
             CpiContext::new(
@@ -72,135 +115,54 @@ impl<'info> PlaySlot<'info> {
                     to: ctx.accounts.casino_vault.to_account_info(),
                 },
             ),
-            amount,
+            terms.player_stake(amount),
         )?;
         
-        let inco = ctx.accounts.inco_lightning_program.to_account_info();
-        let signer = ctx.accounts.player.to_account_info();
-        
-        // Generate 3 reels - each e_rand call produces a unique random value
-        let reel1 = generate_bounded_random(inco.clone(), signer.clone(), 10)?;
-        let reel2 = generate_bounded_random(inco.clone(), signer.clone(), 10)?;
-        let reel3 = generate_bounded_random(inco.clone(), signer.clone(), 10)?;
-        
-        // Check matches - e_eq returns Ebool
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let match12: Ebool = cpi::e_eq(cpi_ctx, reel1, reel2, 0)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let match23: Ebool = cpi::e_eq(cpi_ctx, reel2, reel3, 0)?;
+        ctx.accounts.admin.record_wager(amount, house_edge_bps)?;
         
-        // Payout amounts
-        let jackpot_amount = apply_house_edge(amount.saturating_mul(JACKPOT_MULTIPLIER));
-        let small_amount = apply_house_edge(amount.saturating_mul(SMALL_WIN_MULTIPLIER));
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let enc_jackpot = cpi::as_euint128(cpi_ctx, jackpot_amount as u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let enc_small = cpi::as_euint128(cpi_ctx, small_amount as u128)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
-        
-        // Using nested e_select for tiered payouts:
-        // if match12 and match23 -> jackpot
-        // elif match12 or match23 -> small win
-        // else -> 0
-        
-        // First: if match23 then small_amount else 0
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let level3 = cpi::e_select(cpi_ctx, match23, enc_small, zero, 0)?;
-        
-        // Then: if match12 then (if match23 then jackpot else small_amount) else level3
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let if_match23_jackpot = cpi::e_select(cpi_ctx, match23, enc_jackpot, enc_small, 0)?;
-        
-        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
-        let payout = cpi::e_select(cpi_ctx, match12, if_match23_jackpot, level3, 0)?;
+        // Reject the bet if its worst case doesn't fit the bankroll left after open games
+        let bankroll = ctx.accounts.admin.bankroll(
+            ctx.accounts.casino_vault.lamports(),
+            ctx.accounts.lp_vault.lamports(),
+        )?;
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.exposure.reserve(max_payout, bankroll, max_exposure_bps)?;
         
-        // Log handles BEFORE allowance (so simulation can capture them)
-        msg!("Payout handle: {}", payout.0);
-        msg!("Reel1 handle: {}", reel1.0);
-        msg!("Reel2 handle: {}", reel2.0);
-        msg!("Reel3 handle: {}", reel3.0);
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let jackpot_amount = as_encrypted(&inco, &signer, jackpot_amount as u128)?;
+        let small_amount = as_encrypted(&inco, &signer, small_amount as u128)?;
+        let (payout, [reel1, reel2, reel3]) =
+            encrypted_slot_spin(&inco, &signer, jackpot_amount, small_amount)?;
         
         // Grant decrypt permission for payout and all 3 reels
         // Requires 8 remaining accounts: 2 per handle (allowance PDA + player)
-        if ctx.remaining_accounts.len() >= 8 {
-            // Allow payout handle (accounts 0-1)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[0].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[1].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                payout.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-            
-            // Allow reel1 handle (accounts 2-3)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[2].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[3].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                reel1.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-            
-            // Allow reel2 handle (accounts 4-5)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[4].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[5].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                reel2.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-            
-            // Allow reel3 handle (accounts 6-7)
-            cpi::allow(
-                CpiContext::new(
-                    inco.clone(),
-                    Allow {
-                        allowance_account: ctx.remaining_accounts[6].clone(),
-                        signer: signer.clone(),
-                        allowed_address: ctx.remaining_accounts[7].clone(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                ),
-                reel3.0,
-                true,
-                ctx.accounts.player.key(),
-            )?;
-        }
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[payout.0, reel1.0, reel2.0, reel3.0],
+        )?;
+        
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
         
         ctx.accounts.game.set_inner(GameResult {
             player: ctx.accounts.player.key(),
+            admin: ctx.accounts.admin.key(),
+            mint: Pubkey::default(),
+            confidential: false,
             game_type: GameType::Slot,
             seed,
             bet_amount: amount,
-            slot: Clock::get()?.slot,
+            slot: now_slot,
             choice_handle: 0,
             payout_handle: payout.0,
+            max_payout,
             random_handles: [reel1.0, reel2.0, reel3.0, 0],  // 3 reels
             claimed: false,
             bump: ctx.bumps.game,
@@ -209,3 +171,52 @@ impl<'info> PlaySlot<'info> {
         Ok(())
     }
 }
+
+/// Spin three encrypted reels (0-9 each)
+/// Returns (payout, reels): jackpot_amount for three of a kind, small_amount for two
+/// adjacent matching reels, otherwise 0
+pub(crate) fn encrypted_slot_spin<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    jackpot_amount: Euint128,
+    small_amount: Euint128,
+) -> Result<(Euint128, [Euint128; 3])> {
+    // Generate 3 reels - each e_rand call produces a unique random value
+    let reel1 = generate_bounded_random(inco.clone(), signer.clone(), SLOT_REEL_SYMBOLS as u128)?;
+    let reel2 = generate_bounded_random(inco.clone(), signer.clone(), SLOT_REEL_SYMBOLS as u128)?;
+    let reel3 = generate_bounded_random(inco.clone(), signer.clone(), SLOT_REEL_SYMBOLS as u128)?;
+    
+    // Check matches - e_eq returns Ebool
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let match12: Ebool = cpi::e_eq(cpi_ctx, reel1, reel2, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let match23: Ebool = cpi::e_eq(cpi_ctx, reel2, reel3, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let zero = cpi::as_euint128(cpi_ctx, 0u128)?;
+    
+    // Using nested e_select for tiered payouts:
+    // if match12 and match23 -> jackpot
+    // elif match12 or match23 -> small win
+    // else -> 0
+    
+    // First: if match23 then small_amount else 0
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let level3 = cpi::e_select(cpi_ctx, match23, small_amount, zero, 0)?;
+    
+    // Then: if match12 then (if match23 then jackpot else small_amount) else level3
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_match23_jackpot = cpi::e_select(cpi_ctx, match23, jackpot_amount, small_amount, 0)?;
+    
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let payout = cpi::e_select(cpi_ctx, match12, if_match23_jackpot, level3, 0)?;
+    
+    // Log handles BEFORE allowance (so simulation can capture them)
+    msg!("Payout handle: {}", payout.0);
+    msg!("Reel1 handle: {}", reel1.0);
+    msg!("Reel2 handle: {}", reel2.0);
+    msg!("Reel3 handle: {}", reel3.0);
+    
+    Ok((payout, [reel1, reel2, reel3]))
+}
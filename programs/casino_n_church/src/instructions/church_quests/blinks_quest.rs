@@ -0,0 +1,351 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::attestation::verify_ed25519_attestation;
+use crate::errors::CasinoError;
+use crate::events::{BlinkQuestCompleted, BlinkQuestCreated, QuestVerifierUpdated};
+use crate::faith_token::mint_faith_reward;
+use crate::states::{
+    Admin, BlinkCompletion, BlinkQuest, PlayerProfile, QuestVerifier, MAX_BLINK_QUEST_FAITH,
+    MAX_QUEST_URI_LEN, PAUSE_QUESTS,
+};
+
+// =============================================================================
+// SET QUEST VERIFIER (authority approves or disables an attestation key)
+// =============================================================================
+
+#[derive(Accounts)]
+#[instruction(verifier: Pubkey)]
+pub struct SetQuestVerifier<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = QuestVerifier::DISCRIMINATOR.len() + QuestVerifier::INIT_SPACE,
+        seeds = [b"quest_verifier", admin.key().as_ref(), verifier.as_ref()],
+        bump
+    )]
+    pub quest_verifier: Account<'info, QuestVerifier>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetQuestVerifier<'info> {
+    /// Approve `verifier` for new blink quests, or disable it, which also voids its
+    /// attestations on quests that already name it
+    pub fn set(
+        &mut self,
+        bumps: &SetQuestVerifierBumps,
+        verifier: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        self.quest_verifier.set_inner(QuestVerifier {
+            admin: self.admin.key(),
+            verifier,
+            enabled,
+            bump: bumps.quest_verifier,
+        });
+
+        emit!(QuestVerifierUpdated {
+            admin: self.admin.key(),
+            verifier,
+            enabled,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// CREATE BLINK QUEST (approved verifier, FAITH backed, bounties escrowed up front)
+// =============================================================================
+
+#[derive(Accounts)]
+#[instruction(quest_id: u64, params: BlinkQuestParams)]
+pub struct CreateBlinkQuest<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = treasury,
+        constraint = !admin.is_paused(PAUSE_QUESTS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    /// Receives the quest's FAITH backing, which redeemed free bets are paid from
+    #[account(mut, seeds = [b"promo_vault", admin.key().as_ref()], bump)]
+    pub promo_vault: SystemAccount<'info>,
+    /// Creators must name a verifier the authority has approved
+    #[account(
+        seeds = [b"quest_verifier", admin.key().as_ref(), params.verifier.as_ref()],
+        bump = verifier_approval.bump,
+        constraint = verifier_approval.enabled @ CasinoError::Unauthorized
+    )]
+    pub verifier_approval: Account<'info, QuestVerifier>,
+    #[account(
+        init,
+        payer = creator,
+        space = BlinkQuest::DISCRIMINATOR.len() + BlinkQuest::INIT_SPACE,
+        seeds = [
+            b"blink_quest",
+            admin.key().as_ref(),
+            creator.key().as_ref(),
+            quest_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub quest: Account<'info, BlinkQuest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BlinkQuestParams {
+    /// Off-chain service that checks the task and signs completions
+    pub verifier: Pubkey,
+    pub uri: String,
+    pub faith_reward: u64,
+    /// Lamports per completion, escrowed for every completion at creation
+    pub bounty: u64,
+    pub deadline_slot: u64,
+    pub max_completions: u32,
+}
+
+impl<'info> CreateBlinkQuest<'info> {
+    pub fn create(
+        &mut self,
+        bumps: &CreateBlinkQuestBumps,
+        quest_id: u64,
+        params: BlinkQuestParams,
+    ) -> Result<()> {
+        require!(params.uri.len() <= MAX_QUEST_URI_LEN, CasinoError::UriTooLong);
+        require!(
+            params.faith_reward <= MAX_BLINK_QUEST_FAITH
+                && (params.faith_reward > 0 || params.bounty > 0),
+            CasinoError::InvalidConfig
+        );
+        require!(params.max_completions > 0, CasinoError::InvalidConfig);
+        let now_slot = Clock::get()?.slot;
+        require!(params.deadline_slot > now_slot, CasinoError::QuestExpired);
+        let reward_pool = params
+            .bounty
+            .checked_mul(params.max_completions as u64)
+            .ok_or(CasinoError::Overflow)?;
+
+        // Like writing quests, the creator backs every FAITH a completion mints in the
+        // promo budget, on top of the treasury's flat fee
+        let fee = self.admin.quest_creation_fee;
+        let faith_backing =
+            Admin::quest_faith_backing(params.faith_reward, params.max_completions)?;
+        if fee > 0 {
+            self.transfer_from_creator(self.treasury.to_account_info(), fee)?;
+        }
+        if faith_backing > 0 {
+            self.transfer_from_creator(self.promo_vault.to_account_info(), faith_backing)?;
+        }
+        if reward_pool > 0 {
+            self.transfer_from_creator(self.quest.to_account_info(), reward_pool)?;
+        }
+
+        self.quest.set_inner(BlinkQuest {
+            admin: self.admin.key(),
+            creator: self.creator.key(),
+            verifier: params.verifier,
+            quest_id,
+            uri: params.uri,
+            faith_reward: params.faith_reward,
+            bounty: params.bounty,
+            reward_pool,
+            deadline_slot: params.deadline_slot,
+            max_completions: params.max_completions,
+            completions: 0,
+            bump: bumps.quest,
+        });
+
+        emit!(BlinkQuestCreated {
+            admin: self.admin.key(),
+            quest: self.quest.key(),
+            creator: self.creator.key(),
+            verifier: params.verifier,
+            faith_reward: params.faith_reward,
+            bounty: params.bounty,
+            max_completions: params.max_completions,
+            deadline_slot: params.deadline_slot,
+            fee,
+            faith_backing,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+
+    fn transfer_from_creator(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.creator.to_account_info(),
+                    to,
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+// =============================================================================
+// COMPLETE BLINK QUEST (verifier-attested, once per completer)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct CompleteBlinkQuest<'info> {
+    #[account(mut)]
+    pub completer: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_QUESTS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        has_one = admin @ CasinoError::Unauthorized,
+        constraint = quest.completions < quest.max_completions @ CasinoError::QuestFull
+    )]
+    pub quest: Account<'info, BlinkQuest>,
+    /// A verifier the authority has since disabled can no longer attest
+    #[account(
+        seeds = [b"quest_verifier", admin.key().as_ref(), quest.verifier.as_ref()],
+        bump = verifier_approval.bump,
+        constraint = verifier_approval.enabled @ CasinoError::Unauthorized
+    )]
+    pub verifier_approval: Account<'info, QuestVerifier>,
+    #[account(
+        init,
+        payer = completer,
+        space = BlinkCompletion::DISCRIMINATOR.len() + BlinkCompletion::INIT_SPACE,
+        seeds = [b"blink_completion", quest.key().as_ref(), completer.key().as_ref()],
+        bump
+    )]
+    pub completion: Account<'info, BlinkCompletion>,
+    #[account(
+        init_if_needed,
+        payer = completer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), completer.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    #[account(mut, seeds = [b"faith_mint", admin.key().as_ref()], bump)]
+    pub faith_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = completer,
+        associated_token::mint = faith_mint,
+        associated_token::authority = completer,
+        associated_token::token_program = token_program
+    )]
+    pub completer_faith: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CompleteBlinkQuest<'info> {
+    /// Must directly follow an Ed25519 program instruction in which the quest's verifier
+    /// signs `BlinkQuest::attestation_message(quest, completer)`
+    pub fn complete(&mut self, bumps: &CompleteBlinkQuestBumps) -> Result<()> {
+        let now_slot = Clock::get()?.slot;
+        require!(now_slot <= self.quest.deadline_slot, CasinoError::QuestExpired);
+
+        let quest_key = self.quest.key();
+        let completer = self.completer.key();
+        verify_ed25519_attestation(
+            &self.instructions,
+            &self.quest.verifier,
+            &BlinkQuest::attestation_message(&quest_key, &completer),
+        )?;
+
+        self.profile.open(completer, self.admin.key(), bumps.profile, now_slot);
+        self.profile.record_church(now_slot);
+        let faith = self.quest.faith_reward;
+        let total_faith = mint_faith_reward(
+            &self.token_program,
+            &self.faith_mint,
+            &self.completer_faith,
+            &self.admin,
+            &mut self.profile,
+            now_slot,
+            faith,
+        )?;
+
+        // Bounties come out of the lamports escrowed in the quest account
+        let bounty = self.quest.bounty;
+        if bounty > 0 {
+            self.quest.reward_pool = self
+                .quest
+                .reward_pool
+                .checked_sub(bounty)
+                .ok_or(CasinoError::InsufficientVaultFunds)?;
+            self.quest.sub_lamports(bounty)?;
+            self.completer.add_lamports(bounty)?;
+        }
+        self.quest.completions += 1;
+
+        self.completion.set_inner(BlinkCompletion {
+            quest: quest_key,
+            completer,
+            slot: now_slot,
+            bump: bumps.completion,
+        });
+
+        emit!(BlinkQuestCompleted {
+            quest: quest_key,
+            completer,
+            faith,
+            bounty,
+            total_faith,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// CLOSE BLINK QUEST (creator reclaims unspent bounties)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct CloseBlinkQuest<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// Closed to the creator, returning rent and any unspent reward pool
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ CasinoError::Unauthorized
+    )]
+    pub quest: Account<'info, BlinkQuest>,
+}
+
+impl<'info> CloseBlinkQuest<'info> {
+    pub fn close(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.slot > self.quest.deadline_slot
+                || self.quest.completions == self.quest.max_completions,
+            CasinoError::QuestStillOpen
+        );
+        Ok(())
+    }
+}
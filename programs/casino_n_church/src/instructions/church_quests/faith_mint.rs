@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::Mint;
+
+use crate::errors::CasinoError;
+use crate::events::FaithMintCreated;
+use crate::faith_token::FAITH_DECIMALS;
+use crate::states::Admin;
+
+// =============================================================================
+// CREATE FAITH MINT (Token-2022 mint owned by the admin PDA)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct CreateFaithMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", authority.key().as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    /// Only the admin PDA can mint FAITH; no one can move or burn it without the holder
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"faith_mint", admin.key().as_ref()],
+        bump,
+        mint::decimals = FAITH_DECIMALS,
+        mint::authority = admin,
+        mint::token_program = token_program
+    )]
+    pub faith_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateFaithMint<'info> {
+    pub fn create(&mut self) -> Result<()> {
+        emit!(FaithMintCreated {
+            admin: self.admin.key(),
+            mint: self.faith_mint.key(),
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
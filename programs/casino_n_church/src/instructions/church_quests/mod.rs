@@ -0,0 +1,12 @@
+// Church quests: the FAITH token, writing and social tasks that earn it, and its redemption
+pub mod faith_mint;
+pub use faith_mint::*;
+
+pub mod writing_quest;
+pub use writing_quest::*;
+
+pub mod blinks_quest;
+pub use blinks_quest::*;
+
+pub mod redeem_faith;
+pub use redeem_faith::*;
@@ -0,0 +1,220 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::CasinoError;
+use crate::events::{BoostCancelled, FaithRedeemed, PromoBudgetFunded};
+use crate::faith_token::{burn_faith, burn_faith_decay};
+use crate::payout_math::check_bet_limits;
+use crate::states::{Admin, Boost, BoostKind, PlayerProfile, PAUSE_GAMES};
+
+// =============================================================================
+// FUND PROMO BUDGET (pays for free bets, kept apart from casino_vault)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct FundPromoBudget<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", authority.key().as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(mut, seeds = [b"promo_vault", admin.key().as_ref()], bump)]
+    pub promo_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundPromoBudget<'info> {
+    pub fn fund(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, CasinoError::MinimumBet);
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.authority.to_account_info(),
+                    to: self.promo_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(PromoBudgetFunded {
+            admin: self.admin.key(),
+            amount,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// REDEEM FAITH (burn FAITH for a Boost voucher)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct RedeemFaith<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    /// One voucher at a time; the bet that consumes it, or `cancel_boost`, closes it
+    #[account(
+        init,
+        payer = player,
+        space = Boost::DISCRIMINATOR.len() + Boost::INIT_SPACE,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub boost: Account<'info, Boost>,
+    #[account(mut, seeds = [b"promo_vault", admin.key().as_ref()], bump)]
+    pub promo_vault: SystemAccount<'info>,
+    #[account(mut, seeds = [b"faith_mint", admin.key().as_ref()], bump)]
+    pub faith_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = faith_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub player_faith: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemFaith<'info> {
+    /// Burn the FAITH a voucher of `kind` and `value` costs on stakes up to `max_stake`.
+    /// A free bet's credit moves from the promo budget into the voucher, so it is paid for
+    /// even if the budget runs dry.
+    pub fn redeem(
+        &mut self,
+        bumps: &RedeemFaithBumps,
+        kind: BoostKind,
+        value: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        let faith_cost = kind.faith_cost(value, max_stake).ok_or(CasinoError::InvalidBoost)?;
+        let now_slot = Clock::get()?.slot;
+        let faith = burn_faith_decay(
+            &self.token_program,
+            &self.faith_mint,
+            &self.player_faith,
+            &self.player,
+            &self.admin,
+            &mut self.profile,
+            now_slot,
+        )?;
+        require!(faith >= faith_cost, CasinoError::InsufficientFaith);
+        burn_faith(
+            &self.token_program,
+            &self.faith_mint,
+            &self.player_faith,
+            &self.player,
+            faith_cost,
+        )?;
+        self.profile.sync_faith(faith - faith_cost);
+
+        if kind == BoostKind::FreeBet {
+            check_bet_limits(value, self.admin.min_bet, self.admin.max_bet)?;
+            let reserve = Rent::get()?.minimum_balance(0);
+            require!(
+                self.promo_vault.lamports().saturating_sub(reserve) >= value,
+                CasinoError::InsufficientPromoBudget
+            );
+
+            let admin_key = self.admin.key();
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"promo_vault", admin_key.as_ref(), &[bumps.promo_vault]]];
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.promo_vault.to_account_info(),
+                        to: self.boost.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                value,
+            )?;
+        }
+
+        self.boost.set_inner(Boost {
+            owner: self.player.key(),
+            admin: self.admin.key(),
+            kind,
+            value,
+            max_stake,
+            faith_cost,
+            bump: bumps.boost,
+        });
+
+        emit!(FaithRedeemed {
+            admin: self.admin.key(),
+            owner: self.player.key(),
+            kind,
+            value,
+            max_stake,
+            faith_cost,
+            total_faith: faith - faith_cost,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// CANCEL BOOST (drop an unspent voucher)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct CancelBoost<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds = [b"admin", admin.authority.as_ref()], bump = admin.bump)]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"boost", admin.key().as_ref(), owner.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Account<'info, Boost>,
+    #[account(mut, seeds = [b"promo_vault", admin.key().as_ref()], bump)]
+    pub promo_vault: SystemAccount<'info>,
+}
+
+impl<'info> CancelBoost<'info> {
+    /// Close a voucher no bet will consume, e.g. a free bet no game's limits accept.
+    /// A free bet's credit goes back to the promo budget and the rent to the owner;
+    /// the FAITH burned for it is not refunded.
+    pub fn cancel(&mut self) -> Result<()> {
+        let refunded = if self.boost.kind == BoostKind::FreeBet { self.boost.value } else { 0 };
+        if refunded > 0 {
+            self.boost.sub_lamports(refunded)?;
+            self.promo_vault.add_lamports(refunded)?;
+        }
+
+        emit!(BoostCancelled {
+            admin: self.admin.key(),
+            owner: self.owner.key(),
+            kind: self.boost.kind,
+            value: self.boost.value,
+            refunded,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::CasinoError;
+use crate::events::{SermonSubmitted, SubmissionApproved, WritingQuestCreated};
+use crate::faith_token::mint_faith_reward;
+use crate::states::{
+    Admin, PlayerProfile, SermonSubmission, WritingQuest, MAX_QUEST_URI_LEN,
+    MAX_WRITING_QUEST_FAITH, PAUSE_QUESTS,
+};
+
+// =============================================================================
+// CREATE WRITING QUEST (creator backs the FAITH it can mint)
+// =============================================================================
+
+#[derive(Accounts)]
+#[instruction(quest_id: u64)]
+pub struct CreateWritingQuest<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = treasury,
+        constraint = !admin.is_paused(PAUSE_QUESTS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    /// Receives the quest's FAITH backing, which redeemed free bets are paid from
+    #[account(mut, seeds = [b"promo_vault", admin.key().as_ref()], bump)]
+    pub promo_vault: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = WritingQuest::DISCRIMINATOR.len() + WritingQuest::INIT_SPACE,
+        seeds = [
+            b"writing_quest",
+            admin.key().as_ref(),
+            creator.key().as_ref(),
+            quest_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub quest: Account<'info, WritingQuest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WritingQuestParams {
+    /// Judge of the submissions; may be the creator
+    pub curator: Pubkey,
+    pub prompt_hash: [u8; 32],
+    pub prompt_uri: String,
+    pub faith_reward: u64,
+    pub deadline_slot: u64,
+    pub max_approvals: u32,
+}
+
+impl<'info> CreateWritingQuest<'info> {
+    pub fn create(
+        &mut self,
+        bumps: &CreateWritingQuestBumps,
+        quest_id: u64,
+        params: WritingQuestParams,
+    ) -> Result<()> {
+        require!(params.prompt_uri.len() <= MAX_QUEST_URI_LEN, CasinoError::UriTooLong);
+        require!(
+            params.faith_reward > 0 && params.faith_reward <= MAX_WRITING_QUEST_FAITH,
+            CasinoError::InvalidConfig
+        );
+        require!(params.max_approvals > 0, CasinoError::InvalidConfig);
+        let now_slot = Clock::get()?.slot;
+        require!(params.deadline_slot > now_slot, CasinoError::QuestExpired);
+
+        // The flat fee goes to the treasury; every FAITH the quest can mint is paid into the
+        // promo budget at its free-bet value, never into the bankroll
+        let fee = self.admin.quest_creation_fee;
+        let faith_backing = Admin::quest_faith_backing(params.faith_reward, params.max_approvals)?;
+        self.transfer_from_creator(self.treasury.to_account_info(), fee)?;
+        self.transfer_from_creator(self.promo_vault.to_account_info(), faith_backing)?;
+
+        self.quest.set_inner(WritingQuest {
+            admin: self.admin.key(),
+            creator: self.creator.key(),
+            curator: params.curator,
+            quest_id,
+            prompt_hash: params.prompt_hash,
+            prompt_uri: params.prompt_uri,
+            faith_reward: params.faith_reward,
+            deadline_slot: params.deadline_slot,
+            max_approvals: params.max_approvals,
+            submissions: 0,
+            approvals: 0,
+            bump: bumps.quest,
+        });
+
+        emit!(WritingQuestCreated {
+            admin: self.admin.key(),
+            quest: self.quest.key(),
+            creator: self.creator.key(),
+            curator: params.curator,
+            faith_reward: params.faith_reward,
+            max_approvals: params.max_approvals,
+            deadline_slot: params.deadline_slot,
+            fee,
+            faith_backing,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+
+    fn transfer_from_creator(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.creator.to_account_info(),
+                    to,
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+// =============================================================================
+// SUBMIT SERMON (one per author per quest)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct SubmitSermon<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_QUESTS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(mut, has_one = admin @ CasinoError::Unauthorized)]
+    pub quest: Account<'info, WritingQuest>,
+    #[account(
+        init,
+        payer = author,
+        space = SermonSubmission::DISCRIMINATOR.len() + SermonSubmission::INIT_SPACE,
+        seeds = [b"sermon", quest.key().as_ref(), author.key().as_ref()],
+        bump
+    )]
+    pub submission: Account<'info, SermonSubmission>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitSermon<'info> {
+    pub fn submit(
+        &mut self,
+        bumps: &SubmitSermonBumps,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        require!(uri.len() <= MAX_QUEST_URI_LEN, CasinoError::UriTooLong);
+        let now_slot = Clock::get()?.slot;
+        require!(now_slot <= self.quest.deadline_slot, CasinoError::QuestExpired);
+
+        self.quest.submissions = self
+            .quest
+            .submissions
+            .checked_add(1)
+            .ok_or(CasinoError::Overflow)?;
+        self.submission.set_inner(SermonSubmission {
+            quest: self.quest.key(),
+            author: self.author.key(),
+            content_hash,
+            uri,
+            submitted_slot: now_slot,
+            approved: false,
+            bump: bumps.submission,
+        });
+
+        emit!(SermonSubmitted {
+            quest: self.quest.key(),
+            author: self.author.key(),
+            content_hash,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// APPROVE SUBMISSION (curator mints FAITH to the author)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct ApproveSubmission<'info> {
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_QUESTS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        has_one = admin @ CasinoError::Unauthorized,
+        has_one = curator @ CasinoError::Unauthorized,
+        constraint = quest.approvals < quest.max_approvals @ CasinoError::QuestFull
+    )]
+    pub quest: Account<'info, WritingQuest>,
+    #[account(
+        mut,
+        seeds = [b"sermon", quest.key().as_ref(), submission.author.as_ref()],
+        bump = submission.bump,
+        constraint = !submission.approved @ CasinoError::AlreadyApproved
+    )]
+    pub submission: Account<'info, SermonSubmission>,
+    /// The author's profile, created by the curator on their first approval
+    #[account(
+        init_if_needed,
+        payer = curator,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), submission.author.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    #[account(mut, seeds = [b"faith_mint", admin.key().as_ref()], bump)]
+    pub faith_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The sermon's author, only used to derive their FAITH account
+    #[account(address = submission.author)]
+    pub author: UncheckedAccount<'info>,
+    /// The author's FAITH account, created by the curator on their first approval
+    #[account(
+        init_if_needed,
+        payer = curator,
+        associated_token::mint = faith_mint,
+        associated_token::authority = author,
+        associated_token::token_program = token_program
+    )]
+    pub author_faith: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApproveSubmission<'info> {
+    pub fn approve(&mut self, bumps: &ApproveSubmissionBumps) -> Result<()> {
+        let now_slot = Clock::get()?.slot;
+        let author = self.submission.author;
+        self.profile.open(author, self.admin.key(), bumps.profile, now_slot);
+
+        self.profile.record_church(now_slot);
+
+        // The author doesn't sign, so decay owed for neglecting the Casino comes out of
+        // the reward rather than their balance
+        let faith = self.quest.faith_reward;
+        let total_faith = mint_faith_reward(
+            &self.token_program,
+            &self.faith_mint,
+            &self.author_faith,
+            &self.admin,
+            &mut self.profile,
+            now_slot,
+            faith,
+        )?;
+        self.quest.approvals += 1;
+        self.submission.approved = true;
+
+        emit!(SubmissionApproved {
+            quest: self.quest.key(),
+            author: self.submission.author,
+            faith,
+            total_faith,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+}
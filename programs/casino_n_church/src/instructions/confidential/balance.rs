@@ -0,0 +1,337 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use inco_lightning::{
+    cpi::{self, accounts::{Operation, VerifySignature}},
+    program::IncoLightning,
+    types::{Ebool, Euint128},
+    ID as INCO_LIGHTNING_ID,
+};
+
+use crate::errors::CasinoError;
+use crate::events::{BalanceDeposited, BalanceWithdrawn};
+use crate::inco_helpers::{
+    allow_handles, as_encrypted, parse_handle_to_u128, parse_plaintext_to_bool,
+};
+use crate::states::{Admin, EncryptedBalance, PAUSE_GAMES};
+
+// =============================================================================
+// DEPOSIT (lamports in, encrypted balance up)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct DepositBalance<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    /// Holds the lamports behind every encrypted balance of this casino
+    #[account(mut, seeds = [b"balance_vault", admin.key().as_ref()], bump)]
+    pub balance_vault: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = EncryptedBalance::DISCRIMINATOR.len() + EncryptedBalance::INIT_SPACE,
+        seeds = [b"encrypted_balance", admin.key().as_ref(), depositor.key().as_ref()],
+        bump
+    )]
+    pub balance: Account<'info, EncryptedBalance>,
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositBalance<'info> {
+    /// The deposit itself is public; what the balance does afterwards is not
+    pub fn deposit(
+        ctx: Context<'_, '_, '_, 'info, DepositBalance<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, CasinoError::MinimumBet);
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.balance_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let owner = ctx.accounts.depositor.key();
+        let admin = ctx.accounts.admin.key();
+        credit_balance(
+            &ctx.accounts.inco_lightning_program.to_account_info(),
+            &ctx.accounts.depositor.to_account_info(),
+            &mut ctx.accounts.balance,
+            owner,
+            admin,
+            ctx.bumps.balance,
+            amount,
+        )?;
+
+        allow_handles(
+            &ctx.accounts.inco_lightning_program.to_account_info(),
+            &ctx.accounts.depositor.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            owner,
+            &[ctx.accounts.balance.handle],
+        )?;
+
+        emit!(BalanceDeposited {
+            admin,
+            owner,
+            amount,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// FUND HOUSE BALANCE (bankroll for confidential games)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct FundHouseBalance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"admin", authority.key().as_ref()],
+        bump = admin.bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(mut, seeds = [b"balance_vault", admin.key().as_ref()], bump)]
+    pub balance_vault: SystemAccount<'info>,
+    /// Counterparty of every confidential game; pays wins and collects losses
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = EncryptedBalance::DISCRIMINATOR.len() + EncryptedBalance::INIT_SPACE,
+        seeds = [b"house_balance", admin.key().as_ref()],
+        bump
+    )]
+    pub house_balance: Account<'info, EncryptedBalance>,
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundHouseBalance<'info> {
+    pub fn fund(&mut self, bumps: &FundHouseBalanceBumps, amount: u64) -> Result<()> {
+        require!(amount > 0, CasinoError::MinimumBet);
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.authority.to_account_info(),
+                    to: self.balance_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let owner = self.authority.key();
+        let admin = self.admin.key();
+        credit_balance(
+            &self.inco_lightning_program.to_account_info(),
+            &self.authority.to_account_info(),
+            &mut self.house_balance,
+            owner,
+            admin,
+            bumps.house_balance,
+            amount,
+        )?;
+
+        emit!(BalanceDeposited {
+            admin,
+            owner,
+            amount,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
+
+/// Add a public amount to an encrypted balance, initializing it on first use
+fn credit_balance<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    balance: &mut EncryptedBalance,
+    owner: Pubkey,
+    admin: Pubkey,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let amount = as_encrypted(inco, signer, amount as u128)?;
+    if balance.owner == Pubkey::default() {
+        balance.owner = owner;
+        balance.admin = admin;
+        balance.bump = bump;
+        balance.handle = amount.0;
+    } else {
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        balance.handle = cpi::e_add(cpi_ctx, Euint128(balance.handle), amount, 0)?.0;
+    }
+    Ok(())
+}
+
+// =============================================================================
+// REQUEST WITHDRAW (debit the encrypted balance if it covers the amount)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct RequestBalanceWithdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds = [b"admin", admin.authority.as_ref()], bump = admin.bump)]
+    pub admin: Account<'info, Admin>,
+    /// A player's balance or, for the authority, the house balance
+    #[account(
+        mut,
+        has_one = owner @ CasinoError::Unauthorized,
+        has_one = admin @ CasinoError::Unauthorized,
+        constraint = balance.pending_withdraw == 0 @ CasinoError::WithdrawPending
+    )]
+    pub balance: Account<'info, EncryptedBalance>,
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestBalanceWithdraw<'info> {
+    /// Nobody can see whether the balance covers `amount`, so it is debited by either
+    /// `amount` or 0 and the encrypted outcome is left for the owner to decrypt.
+    /// Remaining accounts grant the owner decrypt access to [outcome, new balance].
+    pub fn request(
+        ctx: Context<'_, '_, '_, 'info, RequestBalanceWithdraw<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, CasinoError::MinimumBet);
+
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.owner.to_account_info();
+        let balance = Euint128(ctx.accounts.balance.handle);
+        let requested = as_encrypted(&inco, &signer, amount as u128)?;
+
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let covered: Ebool = cpi::e_ge(cpi_ctx, balance, requested, 0)?;
+
+        let zero = as_encrypted(&inco, &signer, 0)?;
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let debit = cpi::e_select(cpi_ctx, covered, requested, zero, 0)?;
+
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let remaining = cpi::e_sub(cpi_ctx, balance, debit, 0)?;
+
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.owner.key(),
+            &[covered.0, remaining.0],
+        )?;
+
+        msg!("Withdraw approval handle: {}", covered.0);
+        let balance = &mut ctx.accounts.balance;
+        balance.handle = remaining.0;
+        balance.pending_withdraw = amount;
+        balance.pending_handle = covered.0;
+        Ok(())
+    }
+}
+
+// =============================================================================
+// COMPLETE WITHDRAW (pay out once the outcome is decrypted)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct CompleteBalanceWithdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds = [b"admin", admin.authority.as_ref()], bump = admin.bump)]
+    pub admin: Account<'info, Admin>,
+    #[account(mut, seeds = [b"balance_vault", admin.key().as_ref()], bump)]
+    pub balance_vault: SystemAccount<'info>,
+    /// A player's balance or, for the authority, the house balance
+    #[account(
+        mut,
+        has_one = owner @ CasinoError::Unauthorized,
+        has_one = admin @ CasinoError::Unauthorized,
+        constraint = balance.pending_withdraw > 0 @ CasinoError::NoPendingWithdraw
+    )]
+    pub balance: Account<'info, EncryptedBalance>,
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CompleteBalanceWithdraw<'info> {
+    /// Verify the decrypted outcome of the pending request and, if the balance covered
+    /// it, send the lamports. Either way the request is cleared.
+    pub fn complete(
+        &mut self,
+        bumps: &CompleteBalanceWithdrawBumps,
+        handle: Vec<u8>,
+        plaintext: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            parse_handle_to_u128(&handle)? == self.balance.pending_handle,
+            CasinoError::InvalidDecryptionProof
+        );
+        cpi::is_validsignature(
+            CpiContext::new(
+                self.inco_lightning_program.to_account_info(),
+                VerifySignature {
+                    instructions: self.instructions.to_account_info(),
+                    signer: self.owner.to_account_info(),
+                },
+            ),
+            1,
+            Some(vec![handle]),
+            Some(vec![plaintext.clone()]),
+        )?;
+
+        let amount = self.balance.pending_withdraw;
+        let approved = parse_plaintext_to_bool(&plaintext);
+        if approved {
+            let admin_key = self.admin.key();
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"balance_vault", admin_key.as_ref(), &[bumps.balance_vault]]];
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.balance_vault.to_account_info(),
+                        to: self.owner.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+
+        self.balance.pending_withdraw = 0;
+        self.balance.pending_handle = 0;
+
+        emit!(BalanceWithdrawn {
+            admin: self.admin.key(),
+            owner: self.owner.key(),
+            amount,
+            approved,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+}
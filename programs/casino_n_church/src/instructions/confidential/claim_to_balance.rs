@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    cpi::{self, accounts::Operation},
+    program::IncoLightning,
+    types::Euint128,
+    ID as INCO_LIGHTNING_ID,
+};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::allow_handles,
+    states::{Admin, EncryptedBalance, GameResult, PAUSE_CLAIMS},
+};
+
+// =============================================================================
+// CLAIM TO BALANCE (confidential games, nothing decrypted)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct ClaimToBalance<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_CLAIMS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+
+    #[account(
+        mut,
+        seeds = [b"encrypted_balance", admin.key().as_ref(), player.key().as_ref()],
+        bump = balance.bump
+    )]
+    pub balance: Account<'info, EncryptedBalance>,
+
+    /// Closed on success, refunding its rent to the player
+    #[account(
+        mut,
+        close = player,
+        constraint = game.player == player.key() @ CasinoError::Ed25519Pubkey,
+        constraint = game.admin == admin.key() @ CasinoError::Unauthorized,
+        constraint = game.confidential @ CasinoError::CurrencyMismatch,
+        constraint = !game.claimed @ CasinoError::AlreadyClaimed
+    )]
+    pub game: Account<'info, GameResult>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimToBalance<'info> {
+    /// Credit the game's encrypted payout to the player's balance without decrypting it,
+    /// so wins and losses settle the same way. Remaining accounts grant the player decrypt
+    /// access to the new balance.
+    pub fn claim(ctx: Context<'_, '_, '_, 'info, ClaimToBalance<'info>>) -> Result<()> {
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let new_balance = cpi::e_add(
+            cpi_ctx,
+            Euint128(ctx.accounts.balance.handle),
+            Euint128(ctx.accounts.game.payout_handle),
+            0,
+        )?;
+        ctx.accounts.balance.handle = new_balance.0;
+
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[new_balance.0],
+        )?;
+
+        // Mark as claimed; the account itself is closed to the player on exit
+        ctx.accounts.game.claimed = true;
+
+        msg!("Balance handle: {}", new_balance.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+// Confidential play: encrypted stakes and payouts settled against encrypted balances
+pub mod balance;
+pub use balance::*;
+
+pub mod play_confidential;
+pub use play_confidential::*;
+
+pub mod claim_to_balance;
+pub use claim_to_balance::*;
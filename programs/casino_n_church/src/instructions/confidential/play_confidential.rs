@@ -0,0 +1,368 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    cpi::{self, accounts::Operation},
+    program::IncoLightning,
+    types::{Ebool, Euint128},
+    ID as INCO_LIGHTNING_ID,
+};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::{allow_handles, as_encrypted},
+    instructions::casino_games::{
+        encrypted_aviator_flight, encrypted_coinflip, encrypted_roulette_spin, encrypted_slot_spin,
+        COINFLIP_MULTIPLIER,
+    },
+    payout_math::{roulette_multiplier, BPS},
+    states::{
+        Admin, Boost, BoostTerms, EncryptedBalance, GameConfig, GameResult, GameType, PlayerProfile,
+        LUCK_PER_PLAY, MIN_AVIATOR_TARGET_BPS, PAUSE_GAMES,
+    },
+};
+
+// =============================================================================
+// PLAY CONFIDENTIAL (encrypted stake debited from an EncryptedBalance)
+// =============================================================================
+
+#[derive(Accounts)]
+#[instruction(seed: u128, game_type: GameType)]
+pub struct PlayConfidential<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[game_type.id()]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"encrypted_balance", admin.key().as_ref(), player.key().as_ref()],
+        bump = balance.bump
+    )]
+    pub balance: Account<'info, EncryptedBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"house_balance", admin.key().as_ref()],
+        bump = house_balance.bump
+    )]
+    pub house_balance: Account<'info, EncryptedBalance>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameResult::INIT_SPACE,
+        seeds = [b"game_confidential", player.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: Account<'info, GameResult>,
+
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    /// Voucher from `redeem_faith`, consumed and closed by this bet
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"boost", admin.key().as_ref(), player.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlayConfidential<'info> {
+    /// Play any game with an encrypted stake drawn from the player's encrypted balance.
+    ///
+    /// The stake falls to 0 unless it is within the balance, the game's bet limits and any
+    /// voucher's `max_stake`, and the house balance covers its worst case, so an invalid bet
+    /// is a free, losing game rather than a revealing error. The stake moves to the house
+    /// balance, which sets the encrypted payout aside in the game until `claim_to_balance`.
+    /// `encrypted_choice` is read as in the matching `play_*` instruction (ignored for slot).
+    pub fn play(
+        ctx: Context<'_, '_, '_, 'info, PlayConfidential<'info>>,
+        seed: u128,
+        game_type: GameType,
+        encrypted_choice: Vec<u8>,
+        encrypted_amount: Vec<u8>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.game_config;
+        let terms = BoostTerms::confidential(ctx.accounts.boost.as_deref(), config.house_edge_bps)?;
+        let house_edge_bps = terms.house_edge_bps;
+        // Largest gross multiplier the game can pay, which bounds the house's liability
+        let max_multiplier_bps = match game_type {
+            GameType::Coinflip => COINFLIP_MULTIPLIER * BPS,
+            GameType::Roulette { bet_type } => {
+                roulette_multiplier(bet_type, config.straight_multiplier)? * BPS
+            }
+            GameType::Slot => config.jackpot_multiplier * BPS,
+            GameType::Aviator { target_multiplier_bps } => {
+                require!(
+                    target_multiplier_bps as u64 >= MIN_AVIATOR_TARGET_BPS
+                        && target_multiplier_bps as u64 <= config.max_multiplier_bps,
+                    CasinoError::InvalidBetChoice
+                );
+                target_multiplier_bps as u64
+            }
+        };
+        // A payout multiplier voucher raises the worst case with it
+        let max_multiplier_bps = max_multiplier_bps * terms.payout_bps / BPS;
+
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let balance = Euint128(ctx.accounts.balance.handle);
+        let house = Euint128(ctx.accounts.house_balance.handle);
+        let stake = confidential_stake(
+            &inco,
+            &signer,
+            encrypted_amount,
+            balance,
+            house,
+            (config.min_bet, config.max_bet.min(terms.max_stake)),
+            max_multiplier_bps,
+        )?;
+        let payout_stake = encrypted_payout_basis(&inco, &signer, stake, terms.payout_bps)?;
+
+        let (choice, payout, randoms) = match game_type {
+            GameType::Coinflip => {
+                let win_payout = encrypted_win_payout(
+                    &inco,
+                    &signer,
+                    payout_stake,
+                    COINFLIP_MULTIPLIER,
+                    house_edge_bps,
+                )?;
+                let (choice, flip, payout) =
+                    encrypted_coinflip(&inco, &signer, encrypted_choice, win_payout)?;
+                (choice.0, payout, vec![flip])
+            }
+            GameType::Roulette { bet_type } => {
+                let multiplier = roulette_multiplier(bet_type, config.straight_multiplier)?;
+                let win_payout =
+                    encrypted_win_payout(&inco, &signer, payout_stake, multiplier, house_edge_bps)?;
+                let (choice, spin, payout) = encrypted_roulette_spin(
+                    &inco,
+                    &signer,
+                    bet_type,
+                    encrypted_choice,
+                    win_payout,
+                )?;
+                (choice.0, payout, vec![spin])
+            }
+            GameType::Slot => {
+                let jackpot_amount = encrypted_win_payout(
+                    &inco,
+                    &signer,
+                    payout_stake,
+                    config.jackpot_multiplier,
+                    house_edge_bps,
+                )?;
+                let small_amount = encrypted_win_payout(
+                    &inco,
+                    &signer,
+                    payout_stake,
+                    config.small_win_multiplier,
+                    house_edge_bps,
+                )?;
+                let (payout, reels) =
+                    encrypted_slot_spin(&inco, &signer, jackpot_amount, small_amount)?;
+                (0, payout, reels.to_vec())
+            }
+            GameType::Aviator { target_multiplier_bps } => {
+                // Encrypted counterpart of aviator_stake_factor
+                let edge_factor =
+                    as_encrypted(&inco, &signer, (BPS - house_edge_bps as u64) as u128)?;
+                let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+                let stake_factor = cpi::e_mul(cpi_ctx, payout_stake, edge_factor, 0)?;
+                let (target, crash_point, payout) = encrypted_aviator_flight(
+                    &inco,
+                    &signer,
+                    encrypted_choice,
+                    target_multiplier_bps,
+                    config.max_multiplier_bps,
+                    stake_factor,
+                )?;
+                (target.0, payout, vec![crash_point])
+            }
+        };
+
+        // Settle: the stake leaves the player, the payout leaves the house into the game
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let new_balance = cpi::e_sub(cpi_ctx, balance, stake, 0)?;
+
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let house_with_stake = cpi::e_add(cpi_ctx, house, stake, 0)?;
+
+        let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+        let new_house = cpi::e_sub(cpi_ctx, house_with_stake, payout, 0)?;
+
+        ctx.accounts.balance.handle = new_balance.0;
+        ctx.accounts.house_balance.handle = new_house.0;
+
+        // Grant decrypt for [payout, random results.., new balance]
+        let mut handles = vec![payout.0];
+        handles.extend(randoms.iter().map(|random| random.0));
+        handles.push(new_balance.0);
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &handles,
+        )?;
+
+        let mut random_handles = [0u128; 4];
+        for (slot, random) in random_handles.iter_mut().zip(&randoms) {
+            *slot = random.0;
+        }
+
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
+
+        ctx.accounts.game.set_inner(GameResult {
+            player: ctx.accounts.player.key(),
+            admin: ctx.accounts.admin.key(),
+            mint: Pubkey::default(),
+            confidential: true,
+            game_type,
+            seed,
+            bet_amount: 0, // Hidden; the stake only exists as an encrypted handle
+            slot: now_slot,
+            choice_handle: choice,
+            payout_handle: payout.0,
+            max_payout: 0, // Already set aside from the house balance, nothing to reserve
+            random_handles,
+            claimed: false,
+            bump: ctx.bumps.game,
+        });
+
+        Ok(())
+    }
+}
+
+/// Encrypt the requested stake and zero it unless it fits the player's balance and
+/// `bet_limits` (the game's [min_bet, max_bet], capped at any voucher's `max_stake`) and the
+/// house balance (plus the stake) covers its worst-case payout
+fn confidential_stake<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    encrypted_amount: Vec<u8>,
+    balance: Euint128,
+    house: Euint128,
+    bet_limits: (u64, u64),
+    max_multiplier_bps: u64,
+) -> Result<Euint128> {
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let amount: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_amount, 0)?;
+
+    let zero = as_encrypted(inco, signer, 0)?;
+    let (min_bet, max_bet) = bet_limits;
+    let min_bet = as_encrypted(inco, signer, min_bet as u128)?;
+    let max_bet = as_encrypted(inco, signer, max_bet as u128)?;
+    let max_multiplier = as_encrypted(inco, signer, max_multiplier_bps as u128)?;
+    let bps = as_encrypted(inco, signer, BPS as u128)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let funded: Ebool = cpi::e_le(cpi_ctx, amount, balance, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let above_min: Ebool = cpi::e_ge(cpi_ctx, amount, min_bet, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let below_max: Ebool = cpi::e_le(cpi_ctx, amount, max_bet, 0)?;
+
+    // Worst case = amount * max_multiplier_bps / BPS, owed by the house after taking the stake
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let scaled = cpi::e_mul(cpi_ctx, amount, max_multiplier, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let worst_case = cpi::e_div(cpi_ctx, scaled, bps, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let house_with_stake = cpi::e_add(cpi_ctx, house, amount, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let covered: Ebool = cpi::e_le(cpi_ctx, worst_case, house_with_stake, 0)?;
+
+    // stake = funded ? (above_min ? (below_max ? (covered ? amount : 0) : 0) : 0) : 0
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_covered = cpi::e_select(cpi_ctx, covered, amount, zero, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_below_max = cpi::e_select(cpi_ctx, below_max, if_covered, zero, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let if_above_min = cpi::e_select(cpi_ctx, above_min, if_below_max, zero, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_select(cpi_ctx, funded, if_above_min, zero, 0)
+}
+
+/// Encrypted counterpart of `BoostTerms::payout_basis`: stake * payout_bps / BPS
+fn encrypted_payout_basis<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    stake: Euint128,
+    payout_bps: u64,
+) -> Result<Euint128> {
+    if payout_bps == BPS {
+        return Ok(stake);
+    }
+    let scale = as_encrypted(inco, signer, payout_bps as u128)?;
+    let bps = as_encrypted(inco, signer, BPS as u128)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let scaled = cpi::e_mul(cpi_ctx, stake, scale, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_div(cpi_ctx, scaled, bps, 0)
+}
+
+/// Encrypted counterpart of `calculate_win_payout`: stake * multiplier, net of edge
+fn encrypted_win_payout<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    stake: Euint128,
+    multiplier: u64,
+    house_edge_bps: u16,
+) -> Result<Euint128> {
+    let multiplier = as_encrypted(inco, signer, multiplier as u128)?;
+    let edge = as_encrypted(inco, signer, house_edge_bps as u128)?;
+    let bps = as_encrypted(inco, signer, BPS as u128)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let gross = cpi::e_mul(cpi_ctx, stake, multiplier, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let scaled_edge = cpi::e_mul(cpi_ctx, gross, edge, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    let house_cut = cpi::e_div(cpi_ctx, scaled_edge, bps, 0)?;
+
+    let cpi_ctx = CpiContext::new(inco.clone(), Operation { signer: signer.clone() });
+    cpi::e_sub(cpi_ctx, gross, house_cut, 0)
+}
@@ -1,11 +1,15 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
 
-use crate::states::{Admin, LiquidityPool, LiquidityProvider};
+use crate::states::{
+    Admin, Exposure, LiquidityPool, LiquidityProvider, DEFAULT_WITHDRAW_DELAY_SLOTS,
+    LP_MINT_DECIMALS, PAUSE_LP_DEPOSIT,
+};
 use crate::errors::CasinoError;
-
-const BPS: u64 = 10_000;
-const SLOTS_PER_YEAR: u64 = 63_072_000; // approx, for simple APR calc
-const DEFAULT_YIELD_BPS: u16 = 500; // 5% APR 
+use crate::lp_math::{lp_exposure, pool_assets, shares_for_deposit};
 
 #[derive(Accounts)]
 pub struct LpDeposit<'info> {
@@ -13,7 +17,8 @@ pub struct LpDeposit<'info> {
     pub depositor: Signer<'info>,
     #[account(
         seeds = [b"admin", admin.authority.as_ref()],
-        bump = admin.bump
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_LP_DEPOSIT) @ CasinoError::ProtocolPaused
     )]
     pub admin: Account<'info, Admin>,
     #[account(
@@ -22,6 +27,12 @@ pub struct LpDeposit<'info> {
         bump
     )]
     pub vault: SystemAccount<'info>, //Make sure to init Lp vault in admin init
+    /// Holds the float lent by LPs, which still backs their shares
+    #[account(seeds = [b"casino_vault", admin.key().as_ref()], bump)]
+    pub casino_vault: SystemAccount<'info>,
+    /// Worst-case payouts of unclaimed games, which deposits are priced net of
+    #[account(seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
     #[account(
         init_if_needed,
         payer = depositor,
@@ -30,6 +41,25 @@ pub struct LpDeposit<'info> {
         bump
     )]
     pub pool: Account<'info, LiquidityPool>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [b"lp_mint", admin.key().as_ref()],
+        bump,
+        mint::decimals = LP_MINT_DECIMALS,
+        mint::authority = mint_authority
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    /// CHECK: PDA that signs LP share mints; holds no data
+    #[account(seeds = [b"lp_mint_authority", admin.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = lp_mint,
+        associated_token::authority = depositor
+    )]
+    pub depositor_lp: Account<'info, TokenAccount>,
     #[account(
         init_if_needed,
         payer = depositor,
@@ -38,6 +68,8 @@ pub struct LpDeposit<'info> {
         bump
     )]
     pub provider: Account<'info, LiquidityProvider>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -50,12 +82,31 @@ impl<'info> LpDeposit<'info> {
         if self.pool.admin == Pubkey::default() {
             self.pool.admin = self.admin.key();
             self.pool.vault = self.vault.key();
-            self.pool.yield_bps = DEFAULT_YIELD_BPS;
+            self.pool.lp_mint = self.lp_mint.key();
+            self.pool.last_sweep_slot = now_slot;
+            self.pool.withdraw_delay_slots = DEFAULT_WITHDRAW_DELAY_SLOTS;
             self.pool.bump = bumps.pool;
             self.pool.vault_bump = bumps.vault;
+            self.pool.mint_bump = bumps.lp_mint;
+            self.pool.mint_auth_bump = bumps.mint_authority;
+        }
+        if self.provider.owner == Pubkey::default() {
+            self.provider.bump = bumps.provider;
         }
 
-        self.accrue_yield(now_slot)?;
+        // Price shares before the deposit lands in the vault, as if every open game paid out
+        // its worst case: a depositor must not buy into stakes the pool may still pay back
+        let casino_lamports = self.casino_vault.lamports();
+        let float = self.admin.lp_float_backed(casino_lamports)?;
+        let liability = lp_exposure(
+            self.exposure.outstanding,
+            self.admin.casino_float(casino_lamports)?,
+            float,
+        );
+        let assets = pool_assets(&self.vault.to_account_info(), float)?.saturating_sub(liability);
+        let supply = self.pool.priced_supply(self.lp_mint.supply, assets)?;
+        let shares = shares_for_deposit(amount, assets, supply)?;
+        require!(shares > 0, CasinoError::MinimumBet);
 
         self.provider.balance = self
             .provider
@@ -80,32 +131,24 @@ impl<'info> LpDeposit<'info> {
         let sys_ctx = CpiContext::new(self.system_program.to_account_info(), sys_accounts);
         transfer(sys_ctx, amount)?;
 
-        Ok(())
-    }
+        // Mint shares to the depositor
+        let admin_key = self.admin.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"lp_mint_authority",
+            admin_key.as_ref(),
+            &[bumps.mint_authority],
+        ]];
+        let mint_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.lp_mint.to_account_info(),
+                to: self.depositor_lp.to_account_info(),
+                authority: self.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        mint_to(mint_ctx, shares)?;
 
-    fn accrue_yield(&mut self, now_slot: u64) -> Result<()> {
-        if self.provider.balance == 0 {
-            return Ok(());
-        }
-        let elapsed = now_slot.saturating_sub(self.provider.last_withdraw_slot);
-        if elapsed == 0 {
-            return Ok(());
-        }
-        let yield_bps = self.pool.yield_bps as u64;
-        let accrued = (self.provider.balance as u128)
-            .checked_mul(yield_bps as u128)
-            .ok_or(CasinoError::Overflow)?
-            .checked_mul(elapsed as u128)
-            .ok_or(CasinoError::Overflow)?
-            .checked_div((BPS * SLOTS_PER_YEAR) as u128)
-            .ok_or(CasinoError::Overflow)? as u64;
-
-        self.provider.accrued_yield = self
-            .provider
-            .accrued_yield
-            .checked_add(accrued)
-            .ok_or(CasinoError::Overflow)?;
-        self.provider.last_withdraw_slot = now_slot;
         Ok(())
     }
 }
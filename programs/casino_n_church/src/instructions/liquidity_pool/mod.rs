@@ -4,3 +4,6 @@ pub use deposit::*;
 pub mod withdraw;
 pub use withdraw::*;
 
+
+pub mod rebalance;
+pub use rebalance::*;
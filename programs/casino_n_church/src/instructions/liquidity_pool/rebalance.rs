@@ -0,0 +1,191 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token::Mint;
+
+use crate::states::{Admin, Exposure, LiquidityPool, SWEEP_EPOCH_SLOTS};
+use crate::errors::CasinoError;
+use crate::events::VaultsRebalanced;
+use crate::payout_math::BPS;
+
+#[derive(Accounts)]
+pub struct RebalanceVaults<'info> {
+    /// Anyone may crank a rebalance
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        has_one = treasury,
+        constraint = !admin.paused @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"casino_vault", admin.key().as_ref()],
+        bump
+    )]
+    pub casino_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"lp_vault", admin.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub lp_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", admin.key().as_ref()],
+        bump = pool.bump,
+        has_one = lp_mint
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(
+        seeds = [b"lp_mint", admin.key().as_ref()],
+        bump = pool.mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RebalanceVaults<'info> {
+    /// Keep casino_vault at `Admin.reserve_target`.
+    ///
+    /// Below target, LP capital tops it up and is recorded as `lp_float_lent`,
+    /// which still backs LP shares, so a refill leaves NAV unchanged.
+    /// Above target, the surplus first repays that float, then (once per epoch) realized
+    /// profit is split between the treasury and the LP vault, raising NAV pro rata.
+    /// Profit is only swept out of what open games' maximum payouts leave uncovered.
+    pub fn rebalance(&mut self, bumps: &RebalanceVaultsBumps) -> Result<()> {
+        let now_slot = Clock::get()?.slot;
+        let reserve = Rent::get()?.minimum_balance(0);
+        // Accrued protocol fees are treasury money, not float
+        let casino_free = self.admin.casino_float(self.casino_vault.lamports())?;
+        let target = self.admin.reserve_target;
+
+        let mut refilled = 0;
+        let mut float_repaid = 0;
+        let mut profit_to_lp = 0;
+        let mut profit_to_treasury = 0;
+
+        if casino_free < target {
+            let lp_free = self.lp_vault.lamports().saturating_sub(reserve);
+            refilled = (target - casino_free).min(lp_free);
+            if refilled > 0 {
+                self.transfer_from_lp_vault(refilled)?;
+                self.admin.lp_float_lent = self
+                    .admin
+                    .lp_float_lent
+                    .checked_add(refilled)
+                    .ok_or(CasinoError::Overflow)?;
+            }
+        } else {
+            let mut surplus = casino_free - target;
+
+            float_repaid = surplus.min(self.admin.lp_float_lent);
+            if float_repaid > 0 {
+                self.transfer_from_casino_vault(
+                    bumps,
+                    self.lp_vault.to_account_info(),
+                    float_repaid,
+                )?;
+                self.admin.lp_float_lent -= float_repaid;
+                surplus -= float_repaid;
+            }
+
+            let epoch_elapsed =
+                now_slot >= self.pool.last_sweep_slot.saturating_add(SWEEP_EPOCH_SLOTS);
+            // With no LP shares outstanding there is nobody to pay; profit stays in casino_vault
+            if epoch_elapsed && self.lp_mint.supply > 0 {
+                let bankroll = self
+                    .admin
+                    .bankroll(self.casino_vault.lamports(), self.lp_vault.lamports())?;
+                let profit = surplus
+                    .min(self.admin.unswept_profit())
+                    .min(self.exposure.headroom(bankroll));
+                profit_to_treasury = ((profit as u128)
+                    * (self.admin.treasury_share_bps as u128)
+                    / BPS as u128) as u64;
+                profit_to_lp = profit - profit_to_treasury;
+
+                if profit_to_treasury > 0 {
+                    self.transfer_from_casino_vault(
+                        bumps,
+                        self.treasury.to_account_info(),
+                        profit_to_treasury,
+                    )?;
+                }
+                if profit_to_lp > 0 {
+                    self.transfer_from_casino_vault(
+                        bumps,
+                        self.lp_vault.to_account_info(),
+                        profit_to_lp,
+                    )?;
+                }
+                self.admin.total_profit_swept = self
+                    .admin
+                    .total_profit_swept
+                    .checked_add(profit)
+                    .ok_or(CasinoError::Overflow)?;
+                self.pool.last_sweep_slot = now_slot;
+            }
+        }
+
+        emit!(VaultsRebalanced {
+            admin: self.admin.key(),
+            refilled,
+            float_repaid,
+            profit_to_lp,
+            profit_to_treasury,
+            casino_balance: self.casino_vault.lamports(),
+            slot: now_slot,
+        });
+        Ok(())
+    }
+
+    fn transfer_from_casino_vault(
+        &self,
+        bumps: &RebalanceVaultsBumps,
+        to: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let admin_key = self.admin.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"casino_vault",
+            admin_key.as_ref(),
+            &[bumps.casino_vault],
+        ]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.casino_vault.to_account_info(),
+                    to,
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    fn transfer_from_lp_vault(&self, amount: u64) -> Result<()> {
+        let admin_key = self.admin.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"lp_vault",
+            admin_key.as_ref(),
+            &[self.pool.vault_bump],
+        ]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.lp_vault.to_account_info(),
+                    to: self.casino_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+}
@@ -1,18 +1,314 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token::{self, burn, Burn, Mint, Token, TokenAccount};
 
-use crate::states::{Admin, LiquidityPool, LiquidityProvider};
+use crate::states::{
+    Admin, Exposure, LiquidityPool, LiquidityProvider, WithdrawRequest, PAUSE_LP_WITHDRAW,
+};
 use crate::errors::CasinoError;
+use crate::events::{WithdrawCancelled, WithdrawExecuted, WithdrawRequested, WithdrawRequeued};
+use crate::lp_math::{
+    lamports_for_shares, pool_assets, principal_slice, shares_fillable, vault_liquidity,
+};
 
-const BPS: u64 = 10_000;
-const SLOTS_PER_YEAR: u64 = 63_072_000; // approx
+// =============================================================================
+// REQUEST WITHDRAW (escrow shares and join the queue)
+// =============================================================================
 
 #[derive(Accounts)]
-pub struct LpWithdraw<'info> {
+pub struct RequestWithdraw<'info> {
     #[account(mut)]
     pub depositor: Signer<'info>,
     #[account(
         seeds = [b"admin", admin.authority.as_ref()],
-        bump = admin.bump
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_LP_WITHDRAW) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", admin.key().as_ref()],
+        bump = pool.bump,
+        has_one = lp_mint
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(
+        seeds = [b"lp_mint", admin.key().as_ref()],
+        bump = pool.mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    /// CHECK: PDA that owns the share escrow; holds no data
+    #[account(seeds = [b"lp_mint_authority", admin.key().as_ref()], bump = pool.mint_auth_bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// Holds the shares of every queued request until they are burned
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [b"lp_escrow", admin.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = mint_authority
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = depositor
+    )]
+    pub depositor_lp: Account<'info, TokenAccount>,
+    /// Created for holders who received shares by transfer rather than deposit
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = LiquidityProvider::DISCRIMINATOR.len() + LiquidityProvider::INIT_SPACE,
+        seeds = [b"lp_provider", depositor.key().as_ref(), admin.key().as_ref()],
+        bump
+    )]
+    pub provider: Account<'info, LiquidityProvider>,
+    #[account(
+        init,
+        payer = depositor,
+        space = WithdrawRequest::DISCRIMINATOR.len() + WithdrawRequest::INIT_SPACE,
+        seeds = [b"withdraw_request", admin.key().as_ref(), pool.queue_tail.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestWithdraw<'info> {
+    pub fn request(
+        &mut self,
+        bumps: &RequestWithdrawBumps,
+        shares: u64,
+        allow_partial: bool,
+    ) -> Result<()> {
+        require!(shares > 0, CasinoError::MinimumBet);
+        let held_shares = self.depositor_lp.amount;
+        require!(held_shares >= shares, CasinoError::MaximumBet);
+
+        if self.provider.owner == Pubkey::default() {
+            self.provider.owner = self.depositor.key();
+            self.provider.admin = self.admin.key();
+            self.provider.bump = bumps.provider;
+        }
+
+        let now_slot = Clock::get()?.slot;
+
+        // The request carries its slice of cost basis out of the provider
+        let principal = principal_slice(self.provider.balance, shares, held_shares)?;
+        self.provider.balance -= principal;
+        self.provider.last_withdraw_slot = now_slot;
+
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.depositor_lp.to_account_info(),
+                    to: self.escrow.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+
+        let request_id = self.pool.queue_tail;
+        self.request.set_inner(WithdrawRequest {
+            owner: self.depositor.key(),
+            admin: self.admin.key(),
+            id: request_id,
+            shares,
+            principal,
+            allow_partial,
+            request_slot: now_slot,
+            bump: bumps.request,
+        });
+        self.pool.queue_tail = request_id.checked_add(1).ok_or(CasinoError::Overflow)?;
+        self.pool.queued_shares = self
+            .pool
+            .queued_shares
+            .checked_add(shares)
+            .ok_or(CasinoError::Overflow)?;
+        self.pool.queued_principal = self
+            .pool
+            .queued_principal
+            .checked_add(principal)
+            .ok_or(CasinoError::Overflow)?;
+
+        emit!(WithdrawRequested {
+            admin: self.admin.key(),
+            owner: self.depositor.key(),
+            request_id,
+            shares,
+            allow_partial,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+}
+
+// =============================================================================
+// CANCEL WITHDRAW (take queued shares back)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct CancelWithdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds = [b"admin", admin.authority.as_ref()], bump = admin.bump)]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", admin.key().as_ref()],
+        bump = pool.bump,
+        has_one = lp_mint
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(
+        seeds = [b"lp_mint", admin.key().as_ref()],
+        bump = pool.mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    /// CHECK: PDA that owns the share escrow; holds no data
+    #[account(seeds = [b"lp_mint_authority", admin.key().as_ref()], bump = pool.mint_auth_bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", admin.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = owner
+    )]
+    pub owner_lp: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"lp_provider", owner.key().as_ref(), admin.key().as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, LiquidityProvider>,
+    #[account(
+        mut,
+        seeds = [b"withdraw_request", admin.key().as_ref(), request.id.to_le_bytes().as_ref()],
+        bump = request.bump,
+        has_one = owner @ CasinoError::Unauthorized,
+        constraint = request.shares > 0 @ CasinoError::WithdrawCancelled
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CancelWithdraw<'info> {
+    /// Return a request's unredeemed shares and their cost basis to the owner.
+    ///
+    /// Allowed from anywhere in the queue, even while withdrawals are paused. A cancelled
+    /// head is closed at once; one further back keeps its place, emptied, until
+    /// `execute_withdraw` reaches it and closes it.
+    pub fn cancel(&mut self) -> Result<()> {
+        let (shares, principal) = (self.request.shares, self.request.principal);
+
+        let admin_key = self.admin.key();
+        let authority_seeds: &[&[&[u8]]] = &[&[
+            b"lp_mint_authority",
+            admin_key.as_ref(),
+            &[self.pool.mint_auth_bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow.to_account_info(),
+                    to: self.owner_lp.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                authority_seeds,
+            ),
+            shares,
+        )?;
+
+        self.provider.balance = self
+            .provider
+            .balance
+            .checked_add(principal)
+            .ok_or(CasinoError::Overflow)?;
+        self.pool.queued_shares = self
+            .pool
+            .queued_shares
+            .checked_sub(shares)
+            .ok_or(CasinoError::LpAccountingMismatch)?;
+        self.pool.queued_principal = self
+            .pool
+            .queued_principal
+            .checked_sub(principal)
+            .ok_or(CasinoError::LpAccountingMismatch)?;
+
+        emit!(WithdrawCancelled {
+            admin: admin_key,
+            owner: self.owner.key(),
+            request_id: self.request.id,
+            shares,
+            principal,
+            slot: Clock::get()?.slot,
+        });
+
+        if self.request.id == self.pool.queue_head {
+            self.pool.queue_head += 1;
+            return self.request.close(self.owner.to_account_info());
+        }
+        self.request.shares = 0;
+        self.request.principal = 0;
+        Ok(())
+    }
+}
+
+// =============================================================================
+// EXECUTE WITHDRAW (serve the head of the queue)
+// =============================================================================
+
+/// The head request priced at current NAV, and how much of it liquidity can fill now
+struct HeadQuote {
+    assets: u64,
+    supply: u64,
+    /// Lamports all of the request's shares redeem for
+    owed: u64,
+    /// Shares that can be burned without uncovering open games
+    fillable: u64,
+}
+
+fn quote_head(
+    admin: &Admin,
+    pool: &mut LiquidityPool,
+    vault: &AccountInfo,
+    casino_vault: &AccountInfo,
+    exposure: &Exposure,
+    lp_supply: u64,
+    shares: u64,
+) -> Result<HeadQuote> {
+    // Escrowed shares are still in supply, so queueing leaves NAV unchanged
+    let float = admin.lp_float_backed(casino_vault.lamports())?;
+    let assets = pool_assets(vault, float)?;
+    let supply = pool.priced_supply(lp_supply, assets)?;
+    let owed = lamports_for_shares(shares, assets, supply)?;
+
+    let bankroll = admin.bankroll(casino_vault.lamports(), vault.lamports())?;
+    // Lent float counts towards NAV but is only paid out once rebalancing repays it
+    let available = exposure.headroom(bankroll).min(vault_liquidity(vault)?);
+    Ok(HeadQuote { assets, supply, owed, fillable: shares_fillable(shares, owed, available) })
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdraw<'info> {
+    /// Anyone may serve the queue; proceeds always go to the request owner
+    pub cranker: Signer<'info>,
+    #[account(mut, address = request.owner @ CasinoError::Unauthorized)]
+    pub owner: SystemAccount<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_LP_WITHDRAW) @ CasinoError::ProtocolPaused
     )]
     pub admin: Account<'info, Admin>,
     #[account(
@@ -21,87 +317,250 @@ pub struct LpWithdraw<'info> {
         bump = pool.vault_bump
     )]
     pub vault: SystemAccount<'info>,
+    #[account(seeds = [b"casino_vault", admin.key().as_ref()], bump)]
+    pub casino_vault: SystemAccount<'info>,
+    /// Outstanding liability of unclaimed games, which LP exits may not uncover
+    #[account(seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
     #[account(
         mut,
         seeds = [b"liquidity_pool", admin.key().as_ref()],
-        bump = pool.bump
+        bump = pool.bump,
+        has_one = lp_mint
     )]
     pub pool: Account<'info, LiquidityPool>,
     #[account(
         mut,
-        seeds = [b"lp_provider", depositor.key().as_ref(), admin.key().as_ref()],
-        bump = provider.bump
+        seeds = [b"lp_mint", admin.key().as_ref()],
+        bump = pool.mint_bump
     )]
-    pub provider: Account<'info, LiquidityProvider>,
+    pub lp_mint: Account<'info, Mint>,
+    /// CHECK: PDA that owns the share escrow; holds no data
+    #[account(seeds = [b"lp_mint_authority", admin.key().as_ref()], bump = pool.mint_auth_bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", admin.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"withdraw_request", admin.key().as_ref(), request.id.to_le_bytes().as_ref()],
+        bump = request.bump,
+        constraint = request.id == pool.queue_head @ CasinoError::NotQueueHead
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> LpWithdraw<'info> {
-    pub fn process_withdraw(&mut self, _bumps: &LpWithdrawBumps, amount: u64) -> Result<()> {
-        require!(amount > 0, CasinoError::MinimumBet);
-        require!(self.provider.balance >= amount, CasinoError::MaximumBet);
+impl<'info> ExecuteWithdraw<'info> {
+    /// Redeem the head request at the NAV of now, not of when it was queued.
+    ///
+    /// NAV is vault assets plus lent float over share supply, so yield (or loss) against the
+    /// request's cost basis is whatever the pool really holds, never a promised rate.
+    /// Only what unclaimed games leave uncovered may leave the vault; a partial request
+    /// keeps its remainder queued at the head, an all-or-nothing one fails until it fits
+    /// (or is moved to the back by `requeue_withdraw`).
+    /// The request is closed to its owner once fully filled, or right away if it was
+    /// cancelled while behind the head.
+    pub fn execute(&mut self) -> Result<()> {
+        if self.request.shares == 0 {
+            self.pool.queue_head += 1;
+            return self.request.close(self.owner.to_account_info());
+        }
 
         let now_slot = Clock::get()?.slot;
-        self.accrue_yield(now_slot)?;
+        require!(
+            now_slot >= self.request.request_slot.saturating_add(self.pool.withdraw_delay_slots),
+            CasinoError::WithdrawalCooldown
+        );
 
-        let payout = amount
-            .checked_add(self.provider.accrued_yield)
-            .ok_or(CasinoError::Overflow)?;
+        let HeadQuote { assets, supply, owed, fillable: shares } = quote_head(
+            &self.admin,
+            &mut self.pool,
+            &self.vault,
+            &self.casino_vault,
+            &self.exposure,
+            self.lp_mint.supply,
+            self.request.shares,
+        )?;
+        require!(shares > 0, CasinoError::LiabilityNotCovered);
+        require!(
+            shares == self.request.shares || self.request.allow_partial,
+            CasinoError::WithdrawNotFillable
+        );
+
+        let redeemed = if shares == self.request.shares {
+            owed
+        } else {
+            lamports_for_shares(shares, assets, supply)?
+        };
+        let principal = principal_slice(self.request.principal, shares, self.request.shares)?;
 
-        // Transfer SOL from vault back to depositor
-        let vault_bump = self.pool.vault_bump;
         let admin_key = self.admin.key();
-        let signer_seeds: &[&[&[u8]]] =
-            &[&[b"lp_vault", admin_key.as_ref(), &[vault_bump]]];
+        let authority_seeds: &[&[&[u8]]] = &[&[
+            b"lp_mint_authority",
+            admin_key.as_ref(),
+            &[self.pool.mint_auth_bump],
+        ]];
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.lp_mint.to_account_info(),
+                    from: self.escrow.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                authority_seeds,
+            ),
+            shares,
+        )?;
+
+        // Transfer SOL from vault to the request owner
+        let vault_seeds: &[&[&[u8]]] =
+            &[&[b"lp_vault", admin_key.as_ref(), &[self.pool.vault_bump]]];
         let transfer_ctx = CpiContext::new_with_signer(
             self.system_program.to_account_info(),
             Transfer {
                 from: self.vault.to_account_info(),
-                to: self.depositor.to_account_info(),
+                to: self.owner.to_account_info(),
             },
-            signer_seeds,
+            vault_seeds,
         );
-        transfer(transfer_ctx, payout)?;
+        transfer(transfer_ctx, redeemed)?;
 
-        self.provider.balance = self
-            .provider
-            .balance
-            .checked_sub(amount)
-            .ok_or(CasinoError::Overflow)?;
-        self.provider.accrued_yield = 0;
-        self.provider.last_withdraw_slot = now_slot;
+        self.request.shares -= shares;
+        self.request.principal -= principal;
+        self.pool.queued_shares = self
+            .pool
+            .queued_shares
+            .checked_sub(shares)
+            .ok_or(CasinoError::LpAccountingMismatch)?;
+        self.pool.queued_principal = self
+            .pool
+            .queued_principal
+            .checked_sub(principal)
+            .ok_or(CasinoError::LpAccountingMismatch)?;
         self.pool.total_deposits = self
             .pool
             .total_deposits
-            .saturating_sub(amount);
+            .checked_sub(principal)
+            .ok_or(CasinoError::LpAccountingMismatch)?;
 
-        Ok(())
-    }
+        emit!(WithdrawExecuted {
+            admin: admin_key,
+            owner: self.owner.key(),
+            request_id: self.request.id,
+            shares_burned: shares,
+            lamports: redeemed,
+            principal,
+            shares_remaining: self.request.shares,
+            slot: now_slot,
+        });
 
-    fn accrue_yield(&mut self, now_slot: u64) -> Result<()> {
-        if self.provider.balance == 0 {
-            return Ok(());
-        }
-        let elapsed = now_slot.saturating_sub(self.provider.last_withdraw_slot);
-        if elapsed == 0 {
-            return Ok(());
+        if self.request.shares == 0 {
+            self.pool.queue_head += 1;
+            self.request.close(self.owner.to_account_info())?;
         }
-        let yield_bps = self.pool.yield_bps as u64;
-        let accrued = (self.provider.balance as u128)
-            .checked_mul(yield_bps as u128)
-            .ok_or(CasinoError::Overflow)?
-            .checked_mul(elapsed as u128)
-            .ok_or(CasinoError::Overflow)?
-            .checked_div((BPS * SLOTS_PER_YEAR) as u128)
-            .ok_or(CasinoError::Overflow)? as u64;
-
-        self.provider.accrued_yield = self
-            .provider
-            .accrued_yield
-            .checked_add(accrued)
-            .ok_or(CasinoError::Overflow)?;
-        self.provider.last_withdraw_slot = now_slot;
         Ok(())
     }
 }
 
+// =============================================================================
+// REQUEUE WITHDRAW (move a stuck all-or-nothing head to the back)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct RequeueWithdraw<'info> {
+    /// Anyone may unblock the queue; pays for the new request and gets the old one's rent
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_LP_WITHDRAW) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    #[account(seeds = [b"lp_vault", admin.key().as_ref()], bump = pool.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    #[account(seeds = [b"casino_vault", admin.key().as_ref()], bump)]
+    pub casino_vault: SystemAccount<'info>,
+    #[account(seeds = [b"exposure", admin.key().as_ref()], bump = exposure.bump)]
+    pub exposure: Account<'info, Exposure>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", admin.key().as_ref()],
+        bump = pool.bump,
+        has_one = lp_mint
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(
+        seeds = [b"lp_mint", admin.key().as_ref()],
+        bump = pool.mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        close = cranker,
+        seeds = [b"withdraw_request", admin.key().as_ref(), request.id.to_le_bytes().as_ref()],
+        bump = request.bump,
+        constraint = request.id == pool.queue_head @ CasinoError::NotQueueHead
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+    #[account(
+        init,
+        payer = cranker,
+        space = WithdrawRequest::DISCRIMINATOR.len() + WithdrawRequest::INIT_SPACE,
+        seeds = [b"withdraw_request", admin.key().as_ref(), pool.queue_tail.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub requeued: Account<'info, WithdrawRequest>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequeueWithdraw<'info> {
+    /// Move an all-or-nothing head that liquidity cannot fill to the back of the queue,
+    /// so the requests behind it can be served. It keeps its shares, cost basis and
+    /// request slot, so it owes no second delay.
+    pub fn requeue(&mut self, bumps: &RequeueWithdrawBumps) -> Result<()> {
+        let now_slot = Clock::get()?.slot;
+        require!(
+            now_slot >= self.request.request_slot.saturating_add(self.pool.withdraw_delay_slots),
+            CasinoError::WithdrawalCooldown
+        );
+        let quote = quote_head(
+            &self.admin,
+            &mut self.pool,
+            &self.vault,
+            &self.casino_vault,
+            &self.exposure,
+            self.lp_mint.supply,
+            self.request.shares,
+        )?;
+        require!(
+            !self.request.allow_partial && quote.fillable < self.request.shares,
+            CasinoError::NotRequeueable
+        );
+
+        let request_id = self.pool.queue_tail;
+        self.requeued.set_inner(WithdrawRequest {
+            id: request_id,
+            bump: bumps.requeued,
+            ..self.request.clone().into_inner()
+        });
+        self.pool.queue_head += 1;
+        self.pool.queue_tail = request_id.checked_add(1).ok_or(CasinoError::Overflow)?;
+
+        emit!(WithdrawRequeued {
+            admin: self.admin.key(),
+            owner: self.request.owner,
+            old_request_id: self.request.id,
+            request_id,
+            shares: self.request.shares,
+            slot: now_slot,
+        });
+        Ok(())
+    }
+}
@@ -1,8 +1,14 @@
 pub mod admin;
 pub mod casino_games;
+pub mod church_quests;
+pub mod confidential;
 pub mod liquidity_pool;
+pub mod token_games;
 
 pub use admin::*;
 pub use casino_games::*;
+pub use church_quests::*;
+pub use confidential::*;
 pub use liquidity_pool::*;
+pub use token_games::*;
 
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::verify_decrypted_payout,
+    states::{Admin, GameResult, MintConfig, PlayerProfile, LUCK_PER_WIN, PAUSE_CLAIMS},
+};
+
+// =============================================================================
+// CLAIM TOKEN REWARDS (paid in the mint the game was played in)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct ClaimTokenRewards<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_CLAIMS) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint_config", admin.key().as_ref(), game.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_vault", admin.key().as_ref(), game.mint.as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = player
+    )]
+    pub player_token: Account<'info, TokenAccount>,
+    
+    /// Closed on success, refunding its rent to the player
+    #[account(
+        mut,
+        close = player,
+        constraint = game.player == player.key() @ CasinoError::Ed25519Pubkey,
+        constraint = game.admin == admin.key() @ CasinoError::Unauthorized,
+        constraint = game.mint != Pubkey::default() @ CasinoError::CurrencyMismatch,
+        constraint = !game.claimed @ CasinoError::AlreadyClaimed
+    )]
+    pub game: Account<'info, GameResult>,
+    
+    /// Created when the game was played; winning claims earn LUCK
+    #[account(
+        mut,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimTokenRewards<'info> {
+    /// Claim a token game's verified payout from its mint's vault.
+    ///
+    /// Even a disabled mint pays out its open games.
+    pub fn claim(
+        ctx: Context<ClaimTokenRewards>,
+        handle: Vec<u8>,
+        plaintext: Vec<u8>,
+    ) -> Result<()> {
+        // Verify the decrypted payout on-chain via Inco
+        let payout = verify_decrypted_payout(
+            ctx.accounts.inco_lightning_program.to_account_info(),
+            ctx.accounts.instructions.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.game.payout_handle,
+            handle,
+            plaintext,
+        )?;
+        
+        if payout > 0 {
+            require!(
+                ctx.accounts.token_vault.amount >= payout,
+                CasinoError::InsufficientVaultFunds
+            );
+            
+            let admin_key = ctx.accounts.admin.key();
+            let mint_key = ctx.accounts.mint_config.mint;
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"mint_config",
+                admin_key.as_ref(),
+                mint_key.as_ref(),
+                &[ctx.accounts.mint_config.bump],
+            ]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.token_vault.to_account_info(),
+                        to: ctx.accounts.player_token.to_account_info(),
+                        authority: ctx.accounts.mint_config.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                payout,
+            )?;
+            ctx.accounts.mint_config.record_payout(payout)?;
+        }
+        
+        let luck = if payout > 0 { LUCK_PER_WIN } else { 0 };
+        ctx.accounts.profile.record_casino(Clock::get()?.slot, luck)?;
+        
+        // Mark as claimed; the account itself is closed to the player on exit
+        ctx.accounts.game.claimed = true;
+        let max_payout = ctx.accounts.game.max_payout;
+        ctx.accounts.mint_config.release(max_payout);
+        
+        msg!("Claimed {} tokens!", payout);
+        Ok(())
+    }
+}
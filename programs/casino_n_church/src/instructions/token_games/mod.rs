@@ -0,0 +1,15 @@
+// SPL token versions of the casino games, staked into and paid from per-mint vaults
+pub mod play_coinflip_token;
+pub use play_coinflip_token::*;
+
+pub mod play_roulette_token;
+pub use play_roulette_token::*;
+
+pub mod play_slot_token;
+pub use play_slot_token::*;
+
+pub mod play_aviator_token;
+pub use play_aviator_token::*;
+
+pub mod claim_token_rewards;
+pub use claim_token_rewards::*;
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::{allow_handles, as_encrypted},
+    instructions::casino_games::encrypted_aviator_flight,
+    payout_math::{aviator_stake_factor, calculate_aviator_payout, check_bet_limits},
+    states::{
+        Admin, GameConfig, GameResult, GameType, MintConfig, PlayerProfile, GAME_ID_AVIATOR,
+        LUCK_PER_PLAY, MIN_AVIATOR_TARGET_BPS, PAUSE_GAMES,
+    },
+};
+
+// =============================================================================
+// PLAY AVIATOR (SPL token stake)
+// =============================================================================
+
+#[derive(Accounts)]
+#[instruction(seed: u128)]
+pub struct PlayAviatorToken<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_AVIATOR]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint_config", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.enabled @ CasinoError::MintNotAccepted
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_vault", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint_config.mint,
+        token::authority = player
+    )]
+    pub player_token: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameResult::INIT_SPACE,
+        seeds = [b"game_aviator", player.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: Account<'info, GameResult>,
+    
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlayAviatorToken<'info> {
+    /// Aviator staked in a whitelisted mint (see `PlayAviator` for the target cap)
+    pub fn play(
+        ctx: Context<'_, '_, '_, 'info, PlayAviatorToken<'info>>,
+        seed: u128,
+        encrypted_target_multiplier: Vec<u8>,
+        target_cap_bps: u32,
+        amount: u64,
+    ) -> Result<()> {
+        let mint_config = &ctx.accounts.mint_config;
+        check_bet_limits(amount, mint_config.min_bet, mint_config.max_bet)?;
+        let config = &ctx.accounts.game_config;
+        let house_edge_bps = config.house_edge_bps;
+        let max_multiplier_bps = config.max_multiplier_bps;
+        require!(
+            target_cap_bps as u64 >= MIN_AVIATOR_TARGET_BPS
+                && target_cap_bps as u64 <= max_multiplier_bps,
+            CasinoError::InvalidBetChoice
+        );
+        // Targets above the cap pay nothing, so the cap bounds the payout
+        let max_payout = calculate_aviator_payout(amount, target_cap_bps as u64, house_edge_bps)?;
+        
+        // Transfer bet to the mint's vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.player_token.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // A mint's games are backed only by its own vault
+        let vault_balance = ctx.accounts.token_vault.amount.saturating_add(amount);
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.mint_config.record_wager(amount)?;
+        ctx.accounts.mint_config.reserve(max_payout, vault_balance, max_exposure_bps)?;
+        
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let stake_factor =
+            as_encrypted(&inco, &signer, aviator_stake_factor(amount, house_edge_bps)?)?;
+        let (target, crash_point, payout) = encrypted_aviator_flight(
+            &inco,
+            &signer,
+            encrypted_target_multiplier,
+            target_cap_bps,
+            max_multiplier_bps,
+            stake_factor,
+        )?;
+        
+        // Grant decrypt for BOTH payout and crash point
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[payout.0, crash_point.0],
+        )?;
+        
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
+        
+        ctx.accounts.game.set_inner(GameResult {
+            player: ctx.accounts.player.key(),
+            admin: ctx.accounts.admin.key(),
+            mint: ctx.accounts.mint_config.mint,
+            confidential: false,
+            game_type: GameType::Aviator { target_multiplier_bps: target_cap_bps },
+            seed,
+            bet_amount: amount,
+            slot: now_slot,
+            choice_handle: target.0,
+            payout_handle: payout.0,
+            max_payout,
+            random_handles: [crash_point.0, 0, 0, 0],
+            claimed: false,
+            bump: ctx.bumps.game,
+        });
+        
+        Ok(())
+    }
+}
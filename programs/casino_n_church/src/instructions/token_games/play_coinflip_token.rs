@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    errors::CasinoError,
+    inco_helpers::{allow_handles, as_encrypted},
+    instructions::casino_games::{encrypted_coinflip, COINFLIP_MULTIPLIER},
+    payout_math::{calculate_win_payout, check_bet_limits},
+    states::{
+        Admin, GameConfig, GameResult, GameType, MintConfig, PlayerProfile, GAME_ID_COINFLIP,
+        LUCK_PER_PLAY, PAUSE_GAMES,
+    },
+};
+
+// =============================================================================
+// PLAY COINFLIP (SPL token stake)
+// =============================================================================
+
+#[derive(Accounts)]
+#[instruction(seed: u128)]
+pub struct PlayCoinflipToken<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"admin", admin.authority.as_ref()],
+        bump = admin.bump,
+        constraint = !admin.is_paused(PAUSE_GAMES) @ CasinoError::ProtocolPaused
+    )]
+    pub admin: Account<'info, Admin>,
+    
+    #[account(
+        seeds = [b"game_config", admin.key().as_ref(), &[GAME_ID_COINFLIP]],
+        bump = game_config.bump,
+        constraint = !game_config.paused @ CasinoError::GamePaused
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint_config", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.enabled @ CasinoError::MintNotAccepted
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_vault", admin.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint_config.mint,
+        token::authority = player
+    )]
+    pub player_token: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameResult::INIT_SPACE,
+        seeds = [b"game_coinflip", player.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: Account<'info, GameResult>,
+    
+    /// Created on the player's first bet; every bet earns LUCK
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", admin.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlayCoinflipToken<'info> {
+    /// Coinflip staked in a whitelisted mint, under that mint's bet limits
+    pub fn play(
+        ctx: Context<'_, '_, '_, 'info, PlayCoinflipToken<'info>>,
+        seed: u128,
+        encrypted_choice: Vec<u8>,
+        amount: u64,
+    ) -> Result<()> {
+        let mint_config = &ctx.accounts.mint_config;
+        check_bet_limits(amount, mint_config.min_bet, mint_config.max_bet)?;
+        let house_edge_bps = ctx.accounts.game_config.house_edge_bps;
+        let win_payout = calculate_win_payout(amount, COINFLIP_MULTIPLIER, house_edge_bps)?;
+        let max_payout = win_payout;
+        
+        // Transfer bet to the mint's vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.player_token.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // A mint's games are backed only by its own vault
+        let vault_balance = ctx.accounts.token_vault.amount.saturating_add(amount);
+        let max_exposure_bps = ctx.accounts.admin.max_exposure_bps;
+        ctx.accounts.mint_config.record_wager(amount)?;
+        ctx.accounts.mint_config.reserve(max_payout, vault_balance, max_exposure_bps)?;
+        
+        let inco = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.player.to_account_info();
+        let win_payout = as_encrypted(&inco, &signer, win_payout as u128)?;
+        let (choice, flip, payout) =
+            encrypted_coinflip(&inco, &signer, encrypted_choice, win_payout)?;
+        
+        // Grant player permission to decrypt BOTH payout and flip result
+        allow_handles(
+            &inco,
+            &signer,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.player.key(),
+            &[payout.0, flip.0],
+        )?;
+        
+        let now_slot = Clock::get()?.slot;
+        let (player, admin) = (ctx.accounts.player.key(), ctx.accounts.admin.key());
+        ctx.accounts.profile.open(player, admin, ctx.bumps.profile, now_slot);
+        ctx.accounts.profile.record_casino(now_slot, LUCK_PER_PLAY)?;
+        
+        ctx.accounts.game.set_inner(GameResult {
+            player: ctx.accounts.player.key(),
+            admin: ctx.accounts.admin.key(),
+            mint: ctx.accounts.mint_config.mint,
+            confidential: false,
+            game_type: GameType::Coinflip,
+            seed,
+            bet_amount: amount,
+            slot: now_slot,
+            choice_handle: choice.0,
+            payout_handle: payout.0,
+            max_payout,
+            random_handles: [flip.0, 0, 0, 0],
+            claimed: false,
+            bump: ctx.bumps.game,
+        });
+        
+        Ok(())
+    }
+}
@@ -3,12 +3,24 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod states;
 pub mod errors;
+pub mod events;
 pub mod inco_helpers;
+pub mod payout_math;
+pub mod lp_math;
+pub mod realm_math;
+pub mod attestation;
+pub mod faith_token;
 
 pub use instructions::*;
 pub use states::*;
 pub use errors::*;
+pub use events::*;
 pub use inco_helpers::*;
+pub use payout_math::*;
+pub use lp_math::*;
+pub use realm_math::*;
+pub use attestation::*;
+pub use faith_token::*;
 
 declare_id!("F9wygaMhPNWmCd6MMtZg7orv6ZkvuF4ycWopZ9cjq3Nc");
 
@@ -20,12 +32,132 @@ pub mod casino_n_church {
         ctx.accounts.init(amount, &ctx.bumps)
     }
 
+    /// Upgrade an admin account created by an earlier deployment to the current layout
+    pub fn migrate_admin(ctx: Context<MigrateAdmin>) -> Result<()> {
+        ctx.accounts.migrate(&ctx.bumps)
+    }
+
+    /// Set the quest creation fee and the defaults new game configs start from (authority only)
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        house_edge_bps: u16,
+        quest_creation_fee: u64,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        ctx.accounts.update(house_edge_bps, quest_creation_fee, min_bet, max_bet)
+    }
+
+    pub fn init_game_config(ctx: Context<InitGameConfig>, game_id: u8) -> Result<()> {
+        ctx.accounts.init(game_id, &ctx.bumps)
+    }
+
+    pub fn update_game_config(
+        ctx: Context<UpdateGameConfig>,
+        params: GameConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.update(params)
+    }
+
+    /// Whitelist an SPL mint for betting and create its vault
+    pub fn init_mint_config(
+        ctx: Context<InitMintConfig>,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        ctx.accounts.init(min_bet, max_bet, &ctx.bumps)
+    }
+
+    /// Change a whitelisted mint's bet limits, or disable it for new bets
+    pub fn update_mint_config(
+        ctx: Context<UpdateMintConfig>,
+        min_bet: u64,
+        max_bet: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.update(min_bet, max_bet, enabled)
+    }
+
+    /// Withdraw tokens a mint's vault holds beyond its open games' worst-case payouts
+    pub fn withdraw_token_vault(ctx: Context<WithdrawTokenVault>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount)
+    }
+
+    /// Toggle the global circuit breaker and per-subsystem pause flags (authority or guardian)
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool, pause_flags: u8) -> Result<()> {
+        ctx.accounts.set_pause(paused, pause_flags)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.set_guardian(guardian)
+    }
+
+    pub fn update_vault_config(
+        ctx: Context<UpdateVaultConfig>,
+        reserve_target: u64,
+        treasury_share_bps: u16,
+        max_exposure_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.update(reserve_target, treasury_share_bps, max_exposure_bps)
+    }
+
+    /// Set the protocol fee, in bps of each wager's house edge
+    pub fn update_protocol_fee(
+        ctx: Context<UpdateProtocolFee>,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.update(protocol_fee_bps)
+    }
+
+    /// Pay accrued protocol fees to the treasury (authority only)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount, &ctx.bumps)
+    }
+
+    /// Deposit lamports and mint LP shares at the current NAV
     pub fn lp_deposit(ctx: Context<LpDeposit>, amount: u64) -> Result<()> {
         ctx.accounts.process_deposit(&ctx.bumps, amount)
     }
 
-    pub fn lp_withdraw(ctx: Context<LpWithdraw>, amount: u64) -> Result<()> {
-        ctx.accounts.process_withdraw(&ctx.bumps, amount)
+    /// Escrow LP shares and join the redemption queue; `allow_partial` lets the request
+    /// be filled over several executions as liquidity frees up
+    pub fn request_withdraw(
+        ctx: Context<RequestWithdraw>,
+        shares: u64,
+        allow_partial: bool,
+    ) -> Result<()> {
+        ctx.accounts.request(&ctx.bumps, shares, allow_partial)
+    }
+
+    /// Redeem the head of the queue at NAV once its delay has passed, filling as much
+    /// as liquidity allows (permissionless crank)
+    pub fn execute_withdraw(ctx: Context<ExecuteWithdraw>) -> Result<()> {
+        ctx.accounts.execute()
+    }
+
+    /// Return a queued request's unredeemed shares and cost basis to its owner
+    pub fn cancel_withdraw(ctx: Context<CancelWithdraw>) -> Result<()> {
+        ctx.accounts.cancel()
+    }
+
+    /// Move an all-or-nothing head that liquidity cannot fill to the back of the queue
+    /// (permissionless crank)
+    pub fn requeue_withdraw(ctx: Context<RequeueWithdraw>) -> Result<()> {
+        ctx.accounts.requeue(&ctx.bumps)
+    }
+
+    /// Set the slots a withdraw request waits before it can be executed
+    pub fn update_withdraw_delay(
+        ctx: Context<UpdateWithdrawDelay>,
+        delay_slots: u64,
+    ) -> Result<()> {
+        ctx.accounts.update(delay_slots)
+    }
+
+    /// Refill casino_vault to its reserve target from LPs, or sweep its surplus
+    /// back to LPs and treasury (permissionless crank)
+    pub fn rebalance_vaults(ctx: Context<RebalanceVaults>) -> Result<()> {
+        ctx.accounts.rebalance(&ctx.bumps)
     }
 
     // =========================================================================
@@ -42,14 +174,24 @@ pub mod casino_n_church {
         PlayCoinflip::play(ctx, seed, encrypted_choice, amount)
     }
 
-    /// Play roulette - straight bet on number 0-36
+    /// Play roulette - straight or outside bet (see ROULETTE_BET_* for bet_type)
     pub fn play_roulette<'info>(
         ctx: Context<'_, '_, '_, 'info, PlayRoulette<'info>>,
         seed: u128,
-        encrypted_choice: Vec<u8>,  // Number 0-36 (encrypted)
+        bet_type: u8,
+        encrypted_choice: Vec<u8>,  // Interpreted per bet_type (encrypted)
         amount: u64,
     ) -> Result<()> {
-        PlayRoulette::play(ctx, seed, encrypted_choice, amount)
+        PlayRoulette::play(ctx, seed, bet_type, encrypted_choice, amount)
+    }
+
+    /// Play several roulette bets (straight, split, outside) on one spin
+    pub fn play_roulette_multi<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlayRouletteMulti<'info>>,
+        seed: u128,
+        legs: Vec<RouletteLegInput>,
+    ) -> Result<()> {
+        PlayRouletteMulti::play(ctx, seed, legs)
     }
 
     /// Play slot machine (encrypted reels)
@@ -61,14 +203,15 @@ pub mod casino_n_church {
         PlaySlot::play(ctx, seed, amount)
     }
 
-    /// Play aviator with encrypted target multiplier
+    /// Play aviator with encrypted target multiplier, bounded by a public cap
     pub fn play_aviator<'info>(
         ctx: Context<'_, '_, '_, 'info, PlayAviator<'info>>,
         seed: u128,
         encrypted_target_multiplier: Vec<u8>,
+        target_cap_bps: u32,
         amount: u64,
     ) -> Result<()> {
-        PlayAviator::play(ctx, seed, encrypted_target_multiplier, amount)
+        PlayAviator::play(ctx, seed, encrypted_target_multiplier, target_cap_bps, amount)
     }
 
     /// Claim rewards with on-chain verification (unified)
@@ -79,4 +222,206 @@ pub mod casino_n_church {
     ) -> Result<()> {
         ClaimRewards::claim(ctx, handle, plaintext)
     }
+
+    /// Close a zero-payout, forfeited or expired game and refund its rent to the player
+    pub fn close_lost_game(
+        ctx: Context<CloseLostGame>,
+        proof: Option<PayoutProof>,
+    ) -> Result<()> {
+        CloseLostGame::close(ctx, proof)
+    }
+
+    // =========================================================================
+    // SPL TOKEN GAMES (staked and paid in a whitelisted mint)
+    // =========================================================================
+
+    /// Play coinflip with an encrypted choice, staking tokens
+    pub fn play_coinflip_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlayCoinflipToken<'info>>,
+        seed: u128,
+        encrypted_choice: Vec<u8>,
+        amount: u64,
+    ) -> Result<()> {
+        PlayCoinflipToken::play(ctx, seed, encrypted_choice, amount)
+    }
+
+    /// Play roulette (see ROULETTE_BET_* for bet_type), staking tokens
+    pub fn play_roulette_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlayRouletteToken<'info>>,
+        seed: u128,
+        bet_type: u8,
+        encrypted_choice: Vec<u8>,
+        amount: u64,
+    ) -> Result<()> {
+        PlayRouletteToken::play(ctx, seed, bet_type, encrypted_choice, amount)
+    }
+
+    /// Play slot machine (encrypted reels), staking tokens
+    pub fn play_slot_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaySlotToken<'info>>,
+        seed: u128,
+        amount: u64,
+    ) -> Result<()> {
+        PlaySlotToken::play(ctx, seed, amount)
+    }
+
+    /// Play aviator with an encrypted target multiplier, staking tokens
+    pub fn play_aviator_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlayAviatorToken<'info>>,
+        seed: u128,
+        encrypted_target_multiplier: Vec<u8>,
+        target_cap_bps: u32,
+        amount: u64,
+    ) -> Result<()> {
+        PlayAviatorToken::play(ctx, seed, encrypted_target_multiplier, target_cap_bps, amount)
+    }
+
+    /// Claim a token game's verified payout in the mint it was played in
+    pub fn claim_token_rewards(
+        ctx: Context<ClaimTokenRewards>,
+        handle: Vec<u8>,
+        plaintext: Vec<u8>,
+    ) -> Result<()> {
+        ClaimTokenRewards::claim(ctx, handle, plaintext)
+    }
+
+    // =========================================================================
+    // CONFIDENTIAL PLAY (encrypted balances; stakes and payouts stay hidden)
+    // =========================================================================
+
+    /// Deposit lamports into the caller's encrypted balance
+    pub fn deposit_balance<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositBalance<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        DepositBalance::deposit(ctx, amount)
+    }
+
+    /// Add lamports to the house balance that backs confidential games (authority only)
+    pub fn fund_house_balance(ctx: Context<FundHouseBalance>, amount: u64) -> Result<()> {
+        ctx.accounts.fund(&ctx.bumps, amount)
+    }
+
+    /// Debit `amount` from an encrypted balance if it covers it; the outcome is encrypted
+    pub fn request_balance_withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, RequestBalanceWithdraw<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        RequestBalanceWithdraw::request(ctx, amount)
+    }
+
+    /// Pay out a pending balance withdrawal once its decrypted outcome is verified
+    pub fn complete_balance_withdraw(
+        ctx: Context<CompleteBalanceWithdraw>,
+        handle: Vec<u8>,
+        plaintext: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.complete(&ctx.bumps, handle, plaintext)
+    }
+
+    /// Play any game with an encrypted stake from the caller's encrypted balance
+    pub fn play_confidential<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlayConfidential<'info>>,
+        seed: u128,
+        game_type: GameType,
+        encrypted_choice: Vec<u8>,
+        encrypted_amount: Vec<u8>,
+    ) -> Result<()> {
+        PlayConfidential::play(ctx, seed, game_type, encrypted_choice, encrypted_amount)
+    }
+
+    /// Credit a confidential game's encrypted payout to the player's balance
+    pub fn claim_to_balance<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimToBalance<'info>>,
+    ) -> Result<()> {
+        ClaimToBalance::claim(ctx)
+    }
+
+    // =========================================================================
+    // FAITH TOKEN (Token-2022 mint controlled by the admin PDA)
+    // =========================================================================
+
+    /// Create the FAITH mint that quests mint into and redemptions burn from (authority only)
+    pub fn create_faith_mint(ctx: Context<CreateFaithMint>) -> Result<()> {
+        ctx.accounts.create()
+    }
+
+    // =========================================================================
+    // CHURCH QUESTS (earn FAITH)
+    // =========================================================================
+
+    /// Open a sermon-writing quest, backing the FAITH it can mint in the promo budget
+    pub fn create_writing_quest(
+        ctx: Context<CreateWritingQuest>,
+        quest_id: u64,
+        params: WritingQuestParams,
+    ) -> Result<()> {
+        ctx.accounts.create(&ctx.bumps, quest_id, params)
+    }
+
+    /// Submit a sermon (content hash and off-chain URI) to a writing quest
+    pub fn submit_sermon(
+        ctx: Context<SubmitSermon>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.submit(&ctx.bumps, content_hash, uri)
+    }
+
+    /// Approve a sermon and mint the quest's FAITH to its author (curator only)
+    pub fn approve_submission(ctx: Context<ApproveSubmission>) -> Result<()> {
+        ctx.accounts.approve(&ctx.bumps)
+    }
+
+    /// Approve or disable a key that attests blink quest completions
+    pub fn set_quest_verifier(
+        ctx: Context<SetQuestVerifier>,
+        verifier: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.set(&ctx.bumps, verifier, enabled)
+    }
+
+    /// Open a Blink quest, paying for the FAITH it can mint and escrowing every bounty
+    pub fn create_blink_quest(
+        ctx: Context<CreateBlinkQuest>,
+        quest_id: u64,
+        params: BlinkQuestParams,
+    ) -> Result<()> {
+        ctx.accounts.create(&ctx.bumps, quest_id, params)
+    }
+
+    /// Claim a Blink quest's FAITH and bounty with the verifier's Ed25519 attestation
+    pub fn complete_blink_quest(ctx: Context<CompleteBlinkQuest>) -> Result<()> {
+        ctx.accounts.complete(&ctx.bumps)
+    }
+
+    /// Close an expired or fully completed Blink quest, refunding unspent bounties
+    pub fn close_blink_quest(ctx: Context<CloseBlinkQuest>) -> Result<()> {
+        ctx.accounts.close()
+    }
+
+    // =========================================================================
+    // FAITH REDEMPTION (FAITH → casino boosts)
+    // =========================================================================
+
+    /// Add lamports to the promo budget that pays for free bets (authority only)
+    pub fn fund_promo_budget(ctx: Context<FundPromoBudget>, amount: u64) -> Result<()> {
+        ctx.accounts.fund(amount)
+    }
+
+    /// Burn FAITH for a Boost voucher the player's next SOL bet of up to `max_stake` consumes
+    pub fn redeem_faith(
+        ctx: Context<RedeemFaith>,
+        kind: BoostKind,
+        value: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        ctx.accounts.redeem(&ctx.bumps, kind, value, max_stake)
+    }
+
+    /// Close an unspent Boost voucher, returning free bet credit to the promo budget
+    pub fn cancel_boost(ctx: Context<CancelBoost>) -> Result<()> {
+        ctx.accounts.cancel()
+    }
 }
@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CasinoError;
+
+/// Lamports in the LP vault that can leave it; the rent-exempt minimum is never redeemable
+pub fn vault_liquidity(vault: &AccountInfo) -> Result<u64> {
+    let reserve = Rent::get()?.minimum_balance(0);
+    Ok(vault.lamports().saturating_sub(reserve))
+}
+
+/// LP capital backing shares: vault liquidity plus the float `rebalance_vaults` has lent
+/// to casino_vault (see `Admin::lp_float_backed`), which is repaid before any profit
+pub fn pool_assets(vault: &AccountInfo, lp_float: u64) -> Result<u64> {
+    vault_liquidity(vault)?
+        .checked_add(lp_float)
+        .ok_or(CasinoError::Overflow.into())
+}
+
+/// Worst-case payouts of open games the LP pool would fund: whatever `outstanding` exceeds
+/// the house's own casino float (casino float net of the LP float lent to it), which pays first
+pub fn lp_exposure(outstanding: u64, casino_float: u64, lp_float: u64) -> u64 {
+    outstanding.saturating_sub(casino_float.saturating_sub(lp_float))
+}
+
+/// Shares minted for a deposit at the current NAV (pool assets / share supply).
+/// The first deposit into an empty pool mints 1 share per lamport. Lamports already in the
+/// vault must be priced into `LiquidityPool::priced_supply` first, so they back shares nobody
+/// holds; a pool whose shares are backed by nothing takes no deposits.
+/// Rounds down, in favour of existing holders.
+pub fn shares_for_deposit(amount: u64, pool_assets: u64, share_supply: u64) -> Result<u64> {
+    if share_supply == 0 {
+        require!(pool_assets == 0, CasinoError::LpAccountingMismatch);
+        return Ok(amount);
+    }
+    require!(pool_assets > 0, CasinoError::InsufficientVaultFunds);
+    let shares = (amount as u128)
+        .checked_mul(share_supply as u128)
+        .ok_or(CasinoError::Overflow)?
+        / pool_assets as u128;
+    u64::try_from(shares).map_err(|_| CasinoError::Overflow.into())
+}
+
+/// Lamports redeemed for burning `shares` at the current NAV. Rounds down, in favour of the pool.
+pub fn lamports_for_shares(shares: u64, pool_assets: u64, share_supply: u64) -> Result<u64> {
+    require!(shares <= share_supply, CasinoError::InsufficientVaultFunds);
+    if share_supply == 0 {
+        return Ok(0);
+    }
+    let lamports = (shares as u128)
+        .checked_mul(pool_assets as u128)
+        .ok_or(CasinoError::Overflow)?
+        / share_supply as u128;
+    u64::try_from(lamports).map_err(|_| CasinoError::Overflow.into())
+}
+
+/// Cost basis that leaves a holding of `held` shares (basis `basis`) along with `shares` of them.
+/// Rounds down, so a holding's last share always takes the remaining basis with it.
+pub fn principal_slice(basis: u64, shares: u64, held: u64) -> Result<u64> {
+    require!(shares <= held, CasinoError::MaximumBet);
+    if shares == held {
+        return Ok(basis);
+    }
+    Ok(((basis as u128) * (shares as u128) / held as u128) as u64)
+}
+
+/// Escrowed shares a queued redemption can burn when only `available` lamports may leave:
+/// all of them if `owed` fits, otherwise the proportional slice, rounded down
+pub fn shares_fillable(shares: u64, owed: u64, available: u64) -> u64 {
+    if owed <= available {
+        return shares;
+    }
+    ((shares as u128) * (available as u128) / owed as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SOL: u64 = 1_000_000_000;
+
+    #[test]
+    fn first_deposit_is_one_to_one() {
+        assert_eq!(shares_for_deposit(5 * SOL, 0, 0).unwrap(), 5 * SOL);
+        // Lamports already in the vault back unowned shares, 1:1, and never the depositor's
+        assert!(shares_for_deposit(5 * SOL, 3 * SOL, 0).is_err());
+        assert_eq!(shares_for_deposit(5 * SOL, 3 * SOL, 3 * SOL).unwrap(), 5 * SOL);
+        assert_eq!(lamports_for_shares(5 * SOL, 8 * SOL, 8 * SOL).unwrap(), 5 * SOL);
+        // Shares outstanding against an empty vault cannot be priced
+        assert!(shares_for_deposit(5 * SOL, 0, SOL).is_err());
+    }
+
+    #[test]
+    fn deposits_price_at_nav() {
+        // Pool doubled in value: 10 SOL backing 5 SOL of shares
+        assert_eq!(shares_for_deposit(2 * SOL, 10 * SOL, 5 * SOL).unwrap(), SOL);
+        // Pool lost half: 5 SOL backing 10 SOL of shares
+        assert_eq!(shares_for_deposit(SOL, 5 * SOL, 10 * SOL).unwrap(), 2 * SOL);
+    }
+
+    #[test]
+    fn lp_exposure_is_what_house_float_cannot_cover() {
+        // 4 SOL of casino float, 1 SOL of it lent by LPs: the house covers 3 SOL itself
+        assert_eq!(lp_exposure(2 * SOL, 4 * SOL, SOL), 0);
+        assert_eq!(lp_exposure(5 * SOL, 4 * SOL, SOL), 2 * SOL);
+        // All float lent by LPs: every open payout is theirs
+        assert_eq!(lp_exposure(5 * SOL, SOL, SOL), 5 * SOL);
+        assert_eq!(lp_exposure(0, 0, 0), 0);
+    }
+
+    #[test]
+    fn redemptions_price_at_nav() {
+        assert_eq!(lamports_for_shares(SOL, 10 * SOL, 5 * SOL).unwrap(), 2 * SOL);
+        assert_eq!(lamports_for_shares(5 * SOL, 10 * SOL, 5 * SOL).unwrap(), 10 * SOL);
+        assert!(lamports_for_shares(6 * SOL, 10 * SOL, 5 * SOL).is_err());
+    }
+
+    #[test]
+    fn principal_leaves_with_its_shares() {
+        assert_eq!(principal_slice(3 * SOL, SOL, 3 * SOL).unwrap(), SOL);
+        // The last shares take whatever basis rounding left behind
+        assert_eq!(principal_slice(10, 1, 3).unwrap(), 3);
+        assert_eq!(principal_slice(10, 3, 3).unwrap(), 10);
+        assert!(principal_slice(10, 4, 3).is_err());
+    }
+
+    #[test]
+    fn queued_redemptions_fill_up_to_liquidity() {
+        assert_eq!(shares_fillable(5 * SOL, 10 * SOL, 10 * SOL), 5 * SOL);
+        assert_eq!(shares_fillable(5 * SOL, 10 * SOL, 4 * SOL), 2 * SOL);
+        assert_eq!(shares_fillable(5 * SOL, 10 * SOL, 0), 0);
+    }
+
+    #[test]
+    fn round_trip_never_gains() {
+        let (assets, supply) = (7 * SOL + 3, 3 * SOL + 1);
+        for amount in [1, 999, SOL, 13 * SOL + 7] {
+            let shares = shares_for_deposit(amount, assets, supply).unwrap();
+            let back = lamports_for_shares(shares, assets + amount, supply + shares).unwrap();
+            assert!(back <= amount, "{amount} -> {shares} -> {back}");
+        }
+    }
+
+    const BPS: u64 = 10_000;
+
+    /// Pool accounting driven only through the helpers the instructions use
+    #[derive(Debug, Clone)]
+    enum Op {
+        Deposit { lp: usize, amount: u64 },
+        Request { lp: usize, bps: u64 },
+        Execute { available: u64 },
+        Pnl { gain: bool, amount: u64 },
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0..4usize, 1..100 * SOL).prop_map(|(lp, amount)| Op::Deposit { lp, amount }),
+            (0..4usize, 1..=BPS).prop_map(|(lp, bps)| Op::Request { lp, bps }),
+            (0..200 * SOL).prop_map(|available| Op::Execute { available }),
+            (any::<bool>(), 0..50 * SOL).prop_map(|(gain, amount)| Op::Pnl { gain, amount }),
+        ]
+    }
+
+    #[derive(Default)]
+    struct Model {
+        assets: u64,
+        supply: u64,
+        /// Shares backing lamports no LP put in, as in `LiquidityPool::priced_supply`
+        unowned: u64,
+        total_deposits: u64,
+        queued_principal: u64,
+        /// (shares, cost basis) per LP
+        lps: [(u64, u64); 4],
+        /// (lp, shares, principal) in request order
+        queue: std::collections::VecDeque<(usize, u64, u64)>,
+    }
+
+    impl Model {
+        fn apply(&mut self, op: Op) -> std::result::Result<(), TestCaseError> {
+            match op {
+                Op::Deposit { lp, amount } => {
+                    if self.supply == 0 {
+                        self.unowned = self.assets;
+                    }
+                    let priced = self.supply + self.unowned;
+                    let Ok(shares) = shares_for_deposit(amount, self.assets, priced) else {
+                        return Ok(());
+                    };
+                    if shares == 0 {
+                        return Ok(());
+                    }
+                    self.assets += amount;
+                    self.supply += shares;
+                    self.total_deposits += amount;
+                    self.lps[lp].0 += shares;
+                    self.lps[lp].1 += amount;
+                }
+                Op::Request { lp, bps } => {
+                    let (held, basis) = self.lps[lp];
+                    if held == 0 {
+                        return Ok(());
+                    }
+                    let shares = ((held as u128 * bps as u128 / BPS as u128) as u64).max(1);
+                    let principal = principal_slice(basis, shares, held).unwrap();
+                    self.lps[lp] = (held - shares, basis - principal);
+                    self.queued_principal += principal;
+                    self.queue.push_back((lp, shares, principal));
+                }
+                Op::Execute { available } => {
+                    let Some(&(lp, shares, principal)) = self.queue.front() else {
+                        return Ok(());
+                    };
+                    let priced = self.supply + self.unowned;
+                    let owed = lamports_for_shares(shares, self.assets, priced).unwrap();
+                    let fill = shares_fillable(shares, owed, available.min(self.assets));
+                    if fill == 0 {
+                        return Ok(());
+                    }
+                    let redeemed = lamports_for_shares(fill, self.assets, priced).unwrap();
+                    // Never more than the pro-rata slice of what the vault really holds
+                    prop_assert!(redeemed <= available.min(self.assets));
+                    prop_assert!(
+                        redeemed as u128 * priced as u128 <= fill as u128 * self.assets as u128
+                    );
+
+                    let released = principal_slice(principal, fill, shares).unwrap();
+                    self.assets -= redeemed;
+                    self.supply -= fill;
+                    self.queued_principal -= released;
+                    self.total_deposits = self
+                        .total_deposits
+                        .checked_sub(released)
+                        .ok_or_else(|| TestCaseError::fail("principal underflow"))?;
+                    if fill == shares {
+                        self.queue.pop_front();
+                    } else {
+                        self.queue[0] = (lp, shares - fill, principal - released);
+                    }
+                }
+                Op::Pnl { gain: true, amount } => self.assets += amount,
+                Op::Pnl { gain: false, amount } => self.assets -= amount.min(self.assets),
+            }
+            Ok(())
+        }
+
+        fn check_invariants(&self) -> std::result::Result<(), TestCaseError> {
+            let basis: u64 = self.lps.iter().map(|(_, basis)| basis).sum();
+            prop_assert_eq!(basis + self.queued_principal, self.total_deposits);
+            let held: u64 = self.lps.iter().map(|(shares, _)| shares).sum();
+            let queued: u64 = self.queue.iter().map(|(_, shares, _)| shares).sum();
+            prop_assert_eq!(held + queued, self.supply);
+            let queued_principal: u64 = self.queue.iter().map(|(_, _, principal)| principal).sum();
+            prop_assert_eq!(queued_principal, self.queued_principal);
+            Ok(())
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn pool_accounting_holds_under_any_sequence(ops in prop::collection::vec(op(), 1..64)) {
+            let mut pool = Model::default();
+            for op in ops {
+                pool.apply(op)?;
+                pool.check_invariants()?;
+            }
+        }
+
+        #[test]
+        fn redemptions_never_dilute_those_who_stay(
+            assets in 0..u64::MAX / 4,
+            supply in 1..u64::MAX / 4,
+            bps in 0..=BPS,
+        ) {
+            let shares = (supply as u128 * bps as u128 / BPS as u128) as u64;
+            let redeemed = lamports_for_shares(shares, assets, supply).unwrap();
+            prop_assert!(redeemed <= assets);
+            // NAV after >= NAV before: (assets - redeemed) / (supply - shares) >= assets / supply
+            prop_assert!(
+                (assets - redeemed) as u128 * supply as u128
+                    >= assets as u128 * (supply - shares) as u128
+            );
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CasinoError,
+    states::{
+        ROULETTE_BET_COLOR, ROULETTE_BET_COLUMN, ROULETTE_BET_DOZEN, ROULETTE_BET_HIGH_LOW,
+        ROULETTE_BET_ODD_EVEN, ROULETTE_BET_SPLIT, ROULETTE_BET_STRAIGHT,
+        ROULETTE_DOZEN_COLUMN_MULTIPLIER, ROULETTE_EVEN_MONEY_MULTIPLIER, ROULETTE_SPLIT_MULTIPLIER,
+    },
+};
+
+/// Basis points denominator (100%)
+pub const BPS: u64 = 10_000;
+
+/// Reject bets outside the configured [min_bet, max_bet] range
+pub fn check_bet_limits(amount: u64, min_bet: u64, max_bet: u64) -> Result<()> {
+    require!(amount >= min_bet, CasinoError::MinimumBet);
+    require!(amount <= max_bet, CasinoError::MaximumBet);
+    Ok(())
+}
+
+/// Remove the house edge from a gross payout: amount - amount * edge / BPS
+pub fn apply_house_edge(amount: u64, house_edge_bps: u16) -> Result<u64> {
+    require!(house_edge_bps as u64 <= BPS, CasinoError::InvalidConfig);
+    let edge = (amount as u128)
+        .checked_mul(house_edge_bps as u128)
+        .ok_or(CasinoError::Overflow)?
+        / BPS as u128;
+    Ok(amount - edge as u64)
+}
+
+/// Win payout for a whole-number multiplier (e.g. 2x coinflip, 36x roulette), net of edge
+pub fn calculate_win_payout(amount: u64, multiplier: u64, house_edge_bps: u16) -> Result<u64> {
+    let gross = amount.checked_mul(multiplier).ok_or(CasinoError::Overflow)?;
+    apply_house_edge(gross, house_edge_bps)
+}
+
+/// Win payout for a multiplier expressed in basis points (e.g. 15000 = 1.5x), net of edge
+pub fn calculate_win_payout_bps(
+    amount: u64,
+    multiplier_bps: u64,
+    house_edge_bps: u16,
+) -> Result<u64> {
+    let gross = (amount as u128)
+        .checked_mul(multiplier_bps as u128)
+        .ok_or(CasinoError::Overflow)?
+        / BPS as u128;
+    let gross = u64::try_from(gross).map_err(|_| CasinoError::Overflow)?;
+    apply_house_edge(gross, house_edge_bps)
+}
+
+/// Range of the uniform draw behind an aviator crash point; a power of two, so e_rand modulo
+/// the range stays uniform
+pub const AVIATOR_CRASH_RANGE: u64 = 1 << 32;
+
+/// Aviator crash point for a uniform draw `r` in [0, AVIATOR_CRASH_RANGE):
+/// BPS * R / (R - r), capped at `max_multiplier_bps` (mirrors encrypted_aviator_flight).
+/// It reaches a target T up to the cap with probability floor(BPS * R / T) / R <= BPS / T,
+/// so the distribution is fair and the house edge is only taken in `aviator_stake_factor`.
+pub fn aviator_crash_point(r: u64, max_multiplier_bps: u64) -> u64 {
+    let range = AVIATOR_CRASH_RANGE as u128;
+    let crash = BPS as u128 * range / (range - r as u128);
+    crash.min(max_multiplier_bps as u128) as u64
+}
+
+/// Aviator stake scaled by (BPS - edge); the encrypted target multiplies this on-chain
+pub fn aviator_stake_factor(amount: u64, house_edge_bps: u16) -> Result<u128> {
+    require!(house_edge_bps as u64 <= BPS, CasinoError::InvalidConfig);
+    Ok(amount as u128 * (BPS - house_edge_bps as u64) as u128)
+}
+
+/// Aviator payout for a target multiplier, net of edge, rounded once:
+/// amount * target_bps * (BPS - edge) / BPS^2 (mirrors the e_mul/e_div in play_aviator)
+pub fn calculate_aviator_payout(amount: u64, target_bps: u64, house_edge_bps: u16) -> Result<u64> {
+    let payout = aviator_stake_factor(amount, house_edge_bps)?
+        .checked_mul(target_bps as u128)
+        .ok_or(CasinoError::Overflow)?
+        / (BPS as u128 * BPS as u128);
+    u64::try_from(payout).map_err(|_| CasinoError::Overflow.into())
+}
+
+/// Symbols on each slot reel (every reel is an independent uniform draw)
+pub const SLOT_REEL_SYMBOLS: u64 = 10;
+
+/// Expected slot return per unit staked, in basis points and net of edge. Reels 1-2 and 2-3
+/// both matching (1/S^2) pays the jackpot; exactly one of them matching (2(S-1)/S^2) pays the
+/// small win (mirrors encrypted_slot_spin)
+pub fn slot_return_bps(
+    jackpot_multiplier: u64,
+    small_win_multiplier: u64,
+    house_edge_bps: u16,
+) -> Result<u64> {
+    let pair_odds = 2 * (SLOT_REEL_SYMBOLS - 1);
+    let gross = small_win_multiplier
+        .checked_mul(pair_odds)
+        .and_then(|pairs| pairs.checked_add(jackpot_multiplier))
+        .and_then(|total| total.checked_mul(BPS))
+        .ok_or(CasinoError::Overflow)?
+        / (SLOT_REEL_SYMBOLS * SLOT_REEL_SYMBOLS);
+    apply_house_edge(gross, house_edge_bps)
+}
+
+/// Protocol's cut of a wager's expected house edge: amount * edge * fee_share / BPS^2
+pub fn protocol_fee(amount: u64, house_edge_bps: u16, protocol_fee_bps: u16) -> Result<u64> {
+    require!(protocol_fee_bps as u64 <= BPS, CasinoError::InvalidConfig);
+    let fee = (amount as u128)
+        .checked_mul(house_edge_bps as u128 * protocol_fee_bps as u128)
+        .ok_or(CasinoError::Overflow)?
+        / (BPS as u128 * BPS as u128);
+    u64::try_from(fee).map_err(|_| CasinoError::Overflow.into())
+}
+
+/// Largest worst-case payout a single bet may carry: `max_exposure_bps` of the available bankroll
+pub fn exposure_limit(available_bankroll: u64, max_exposure_bps: u16) -> u64 {
+    ((available_bankroll as u128) * (max_exposure_bps as u128) / BPS as u128) as u64
+}
+
+/// Gross multiplier for a roulette bet type
+pub fn roulette_multiplier(bet_type: u8, straight_multiplier: u64) -> Result<u64> {
+    match bet_type {
+        ROULETTE_BET_STRAIGHT => Ok(straight_multiplier),
+        ROULETTE_BET_COLOR | ROULETTE_BET_ODD_EVEN | ROULETTE_BET_HIGH_LOW => {
+            Ok(ROULETTE_EVEN_MONEY_MULTIPLIER)
+        }
+        ROULETTE_BET_DOZEN | ROULETTE_BET_COLUMN => Ok(ROULETTE_DOZEN_COLUMN_MULTIPLIER),
+        ROULETTE_BET_SPLIT => Ok(ROULETTE_SPLIT_MULTIPLIER),
+        _ => err!(CasinoError::InvalidBetType),
+    }
+}
+
+/// Plaintext mirror of the encrypted category computed in play_roulette.
+/// A bet wins when the player's choice equals the category; outside bets lose on 0 (None).
+pub fn roulette_category(bet_type: u8, spin: u64) -> Option<u64> {
+    if bet_type == ROULETTE_BET_STRAIGHT {
+        return Some(spin);
+    }
+    if spin == 0 {
+        return None;
+    }
+    let parity = spin % 2;
+    match bet_type {
+        // Odd numbers are red in 1-10 and 19-28, even numbers in 11-18 and 29-36
+        ROULETTE_BET_COLOR => {
+            let flipped = (spin + 17) % 18 >= 10;
+            Some(if flipped { parity } else { 1 - parity })
+        }
+        ROULETTE_BET_ODD_EVEN => Some(parity),
+        ROULETTE_BET_HIGH_LOW => Some((spin >= 19) as u64),
+        ROULETTE_BET_DOZEN => Some((spin + 35) / 12 % 3),
+        ROULETTE_BET_COLUMN => Some((spin + 2) % 3),
+        _ => None,
+    }
+}
+
+/// Plaintext mirror of whether a roulette bet wins (split choices encode first * 37 + second)
+pub fn roulette_bet_wins(bet_type: u8, choice: u64, spin: u64) -> bool {
+    if bet_type == ROULETTE_BET_SPLIT {
+        return choice / 37 == spin || choice % 37 == spin;
+    }
+    roulette_category(bet_type, spin) == Some(choice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::{
+        DEFAULT_HOUSE_EDGE_BPS, DEFAULT_SLOT_JACKPOT_MULTIPLIER, DEFAULT_SLOT_SMALL_WIN_MULTIPLIER,
+        MAX_AVIATOR_MULTIPLIER_BPS, MIN_AVIATOR_TARGET_BPS,
+    };
+
+    const SOL: u64 = 1_000_000_000;
+
+    #[test]
+    fn bet_limits_are_inclusive() {
+        assert!(check_bet_limits(10, 10, 100).is_ok());
+        assert!(check_bet_limits(100, 10, 100).is_ok());
+        assert_eq!(
+            check_bet_limits(9, 10, 100).unwrap_err(),
+            CasinoError::MinimumBet.into()
+        );
+        assert_eq!(
+            check_bet_limits(101, 10, 100).unwrap_err(),
+            CasinoError::MaximumBet.into()
+        );
+    }
+
+    #[test]
+    fn house_edge_is_taken_from_gross() {
+        assert_eq!(apply_house_edge(SOL, 150).unwrap(), 985_000_000);
+        assert_eq!(apply_house_edge(SOL, 0).unwrap(), SOL);
+        assert_eq!(apply_house_edge(SOL, BPS as u16).unwrap(), 0);
+        // Edge rounds down in the player's favour
+        assert_eq!(apply_house_edge(66, 150).unwrap(), 66);
+    }
+
+    #[test]
+    fn house_edge_above_100_percent_is_rejected() {
+        assert!(apply_house_edge(SOL, BPS as u16 + 1).is_err());
+    }
+
+    #[test]
+    fn whole_multiplier_payouts() {
+        // Coinflip 2x with 1.5% edge
+        assert_eq!(calculate_win_payout(SOL, 2, 150).unwrap(), 1_970_000_000);
+        // Roulette straight 36x with 1.5% edge
+        assert_eq!(calculate_win_payout(SOL, 36, 150).unwrap(), 35_460_000_000);
+        assert!(calculate_win_payout(u64::MAX, 2, 150).is_err());
+    }
+
+    #[test]
+    fn bps_multiplier_payouts() {
+        // 1.5x with no edge
+        assert_eq!(calculate_win_payout_bps(SOL, 15_000, 0).unwrap(), 1_500_000_000);
+        // 10x with 1.5% edge
+        assert_eq!(calculate_win_payout_bps(SOL, 100_000, 150).unwrap(), 9_850_000_000);
+        assert!(calculate_win_payout_bps(u64::MAX, 20_000, 0).is_err());
+        // Large intermediate product fits through u128
+        assert_eq!(calculate_win_payout_bps(u64::MAX, BPS, 0).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn protocol_fee_is_a_share_of_the_edge() {
+        // 20% of a 1.5% edge on 1 SOL
+        assert_eq!(protocol_fee(SOL, 150, 2_000).unwrap(), 3_000_000);
+        assert_eq!(protocol_fee(SOL, 150, 0).unwrap(), 0);
+        assert_eq!(protocol_fee(SOL, 0, 2_000).unwrap(), 0);
+        assert!(protocol_fee(SOL, 150, BPS as u16 + 1).is_err());
+    }
+
+    #[test]
+    fn exposure_limit_is_a_fraction_of_bankroll() {
+        assert_eq!(exposure_limit(200 * SOL, 500), 10 * SOL);
+        assert_eq!(exposure_limit(200 * SOL, 0), 0);
+        assert_eq!(exposure_limit(u64::MAX, BPS as u16), u64::MAX);
+    }
+
+    #[test]
+    fn roulette_categories_match_the_table() {
+        const RED: [u64; 18] = [1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36];
+        for spin in 1..=36u64 {
+            let color = if RED.contains(&spin) { 0 } else { 1 };
+            assert_eq!(roulette_category(ROULETTE_BET_COLOR, spin), Some(color), "color {spin}");
+            assert_eq!(roulette_category(ROULETTE_BET_ODD_EVEN, spin), Some(spin % 2));
+            assert_eq!(roulette_category(ROULETTE_BET_HIGH_LOW, spin), Some((spin > 18) as u64));
+            assert_eq!(roulette_category(ROULETTE_BET_DOZEN, spin), Some((spin - 1) / 12));
+            assert_eq!(roulette_category(ROULETTE_BET_COLUMN, spin), Some((spin - 1) % 3));
+            assert_eq!(roulette_category(ROULETTE_BET_STRAIGHT, spin), Some(spin));
+        }
+        // Zero only pays straight bets
+        assert_eq!(roulette_category(ROULETTE_BET_STRAIGHT, 0), Some(0));
+        for bet_type in ROULETTE_BET_COLOR..=ROULETTE_BET_COLUMN {
+            assert_eq!(roulette_category(bet_type, 0), None);
+        }
+    }
+
+    #[test]
+    fn roulette_multipliers_by_bet_type() {
+        assert_eq!(roulette_multiplier(ROULETTE_BET_STRAIGHT, 36).unwrap(), 36);
+        assert_eq!(roulette_multiplier(ROULETTE_BET_COLOR, 36).unwrap(), 2);
+        assert_eq!(roulette_multiplier(ROULETTE_BET_ODD_EVEN, 36).unwrap(), 2);
+        assert_eq!(roulette_multiplier(ROULETTE_BET_HIGH_LOW, 36).unwrap(), 2);
+        assert_eq!(roulette_multiplier(ROULETTE_BET_DOZEN, 36).unwrap(), 3);
+        assert_eq!(roulette_multiplier(ROULETTE_BET_COLUMN, 36).unwrap(), 3);
+        assert_eq!(roulette_multiplier(ROULETTE_BET_SPLIT, 36).unwrap(), 18);
+        assert!(roulette_multiplier(ROULETTE_BET_SPLIT + 1, 36).is_err());
+    }
+
+    #[test]
+    fn roulette_split_covers_both_numbers() {
+        let split_17_20 = 17 * 37 + 20;
+        assert!(roulette_bet_wins(ROULETTE_BET_SPLIT, split_17_20, 17));
+        assert!(roulette_bet_wins(ROULETTE_BET_SPLIT, split_17_20, 20));
+        assert!(!roulette_bet_wins(ROULETTE_BET_SPLIT, split_17_20, 18));
+        // 0-1 split wins on green
+        assert!(roulette_bet_wins(ROULETTE_BET_SPLIT, 1, 0));
+        assert!(roulette_bet_wins(ROULETTE_BET_STRAIGHT, 0, 0));
+        assert!(!roulette_bet_wins(ROULETTE_BET_COLOR, 0, 0));
+    }
+
+    #[test]
+    fn aviator_payout_scales_with_target() {
+        assert_eq!(calculate_aviator_payout(SOL, 20_000, 0).unwrap(), 2 * SOL);
+        assert_eq!(calculate_aviator_payout(SOL, 15_000, 150).unwrap(), 1_477_500_000);
+        assert_eq!(
+            calculate_aviator_payout(SOL, 100_000, 150).unwrap(),
+            calculate_win_payout_bps(SOL, 100_000, 150).unwrap()
+        );
+        assert!(calculate_aviator_payout(u64::MAX, 20_000, 0).is_err());
+    }
+
+    #[test]
+    fn aviator_bets_lose_money_on_average_at_every_target() {
+        let range = AVIATOR_CRASH_RANGE;
+        for target in (MIN_AVIATOR_TARGET_BPS..=MAX_AVIATOR_MULTIPLIER_BPS).step_by(997) {
+            // The crash point rises with the draw, so exactly the top `wins` draws reach target
+            let wins = (BPS as u128 * range as u128 / target as u128) as u64;
+            assert!(aviator_crash_point(range - wins, MAX_AVIATOR_MULTIPLIER_BPS) >= target);
+            assert!(aviator_crash_point(range - wins - 1, MAX_AVIATOR_MULTIPLIER_BPS) < target);
+
+            for edge in [0, DEFAULT_HOUSE_EDGE_BPS] {
+                let payout = calculate_aviator_payout(SOL, target, edge).unwrap() as u128;
+                let expected = payout * wins as u128 / range as u128;
+                let fair = SOL as u128 * (BPS - edge as u64) as u128 / BPS as u128;
+                assert!(expected <= fair, "target {target} returns {expected}");
+            }
+        }
+        assert_eq!(aviator_crash_point(0, MAX_AVIATOR_MULTIPLIER_BPS), BPS);
+        assert_eq!(aviator_crash_point(range - 1, 20_000), 20_000);
+    }
+
+    #[test]
+    fn slot_return_counts_every_reel_outcome() {
+        // Brute force all 1000 spins and compare with the closed form
+        let s = SLOT_REEL_SYMBOLS;
+        let (mut jackpots, mut pairs) = (0, 0);
+        for spin in 0..s * s * s {
+            let (r1, r2, r3) = (spin / (s * s), spin / s % s, spin % s);
+            match (r1 == r2, r2 == r3) {
+                (true, true) => jackpots += 1,
+                (true, false) | (false, true) => pairs += 1,
+                _ => {}
+            }
+        }
+        assert_eq!((jackpots, pairs), (s, 2 * s * (s - 1)));
+        assert_eq!(slot_return_bps(50, 5, 0).unwrap(), 14_000);
+        assert_eq!(slot_return_bps(100, 0, 0).unwrap(), BPS);
+    }
+
+    #[test]
+    fn default_slot_multipliers_lose_money_on_average() {
+        let fair = BPS - DEFAULT_HOUSE_EDGE_BPS as u64;
+        let rtp = slot_return_bps(
+            DEFAULT_SLOT_JACKPOT_MULTIPLIER,
+            DEFAULT_SLOT_SMALL_WIN_MULTIPLIER,
+            DEFAULT_HOUSE_EDGE_BPS,
+        )
+        .unwrap();
+        assert!(rtp < fair, "default slot returns {rtp} bps");
+    }
+}
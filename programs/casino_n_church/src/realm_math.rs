@@ -0,0 +1,92 @@
+use crate::{
+    payout_math::BPS,
+    states::{REALM_DECAY_BPS, REALM_DECAY_PERIOD_SLOTS, REALM_GRACE_SLOTS},
+};
+
+/// Decay periods after which any balance is treated as fully decayed
+const MAX_DECAY_PERIODS: u64 = 64;
+
+/// Points left after `periods` decay steps of REALM_DECAY_BPS each, rounded down every step
+pub fn decay_points(points: u64, periods: u64) -> u64 {
+    if periods >= MAX_DECAY_PERIODS {
+        return 0;
+    }
+    let mut left = points;
+    for _ in 0..periods {
+        left = ((left as u128) * (BPS - REALM_DECAY_BPS) as u128 / BPS as u128) as u64;
+    }
+    left
+}
+
+/// Apply the decay owed on `points` at `now_slot` when the opposite realm was last visited at
+/// `neglected_since` and decay was last settled at `settled_slot`.
+/// Decay starts REALM_GRACE_SLOTS after the last visit and is charged in whole periods only,
+/// so the returned settled slot keeps the partial period for the next settlement.
+pub fn settle_decay(
+    points: u64,
+    neglected_since: u64,
+    settled_slot: u64,
+    now_slot: u64,
+) -> (u64, u64) {
+    let start = settled_slot.max(neglected_since.saturating_add(REALM_GRACE_SLOTS));
+    if now_slot <= start {
+        return (points, settled_slot);
+    }
+    let periods = (now_slot - start) / REALM_DECAY_PERIOD_SLOTS;
+    if periods == 0 {
+        return (points, settled_slot);
+    }
+    (
+        decay_points(points, periods),
+        start + periods * REALM_DECAY_PERIOD_SLOTS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_decay_within_the_grace_period() {
+        assert_eq!(
+            settle_decay(1_000, 100, 100, 100 + REALM_GRACE_SLOTS),
+            (1_000, 100)
+        );
+        assert_eq!(
+            settle_decay(
+                1_000,
+                100,
+                100,
+                100 + REALM_GRACE_SLOTS + REALM_DECAY_PERIOD_SLOTS - 1
+            ),
+            (1_000, 100)
+        );
+    }
+
+    #[test]
+    fn decay_is_charged_per_whole_period() {
+        let start = REALM_GRACE_SLOTS;
+        let (points, settled) = settle_decay(1_000, 0, 0, start + 2 * REALM_DECAY_PERIOD_SLOTS + 7);
+        assert_eq!(points, decay_points(1_000, 2));
+        assert_eq!(settled, start + 2 * REALM_DECAY_PERIOD_SLOTS);
+        // Settling again later charges only the periods since the last settlement
+        let (again, _) = settle_decay(points, 0, settled, settled + REALM_DECAY_PERIOD_SLOTS);
+        assert_eq!(again, decay_points(1_000, 3));
+    }
+
+    #[test]
+    fn visiting_the_other_realm_restarts_the_grace_period() {
+        let now = 10 * REALM_DECAY_PERIOD_SLOTS;
+        assert_eq!(settle_decay(1_000, now - 1, 0, now), (1_000, 0));
+    }
+
+    #[test]
+    fn long_neglect_decays_to_zero() {
+        assert_eq!(decay_points(u64::MAX, MAX_DECAY_PERIODS), 0);
+        assert_eq!(decay_points(1_000, 0), 1_000);
+        assert_eq!(
+            decay_points(1_000, 1),
+            1_000 * (BPS - REALM_DECAY_BPS) / BPS
+        );
+    }
+}
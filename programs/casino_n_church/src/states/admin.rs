@@ -1,10 +1,89 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::CasinoError;
+use crate::payout_math::protocol_fee;
+use crate::states::FREE_BET_LAMPORTS_PER_FAITH;
 
+/// Upper bound for the house edge (10%)
+pub const MAX_HOUSE_EDGE_BPS: u16 = 1_000;
+/// Upper bound for the quest creation fee (10 SOL)
+pub const MAX_QUEST_CREATION_FEE: u64 = 10_000_000_000;
+/// Default house edge (1.5%)
+pub const DEFAULT_HOUSE_EDGE_BPS: u16 = 150;
+/// Default quest creation fee (0.1 SOL)
+pub const DEFAULT_QUEST_CREATION_FEE: u64 = 100_000_000;
+/// Default minimum bet (0.01 SOL)
+pub const DEFAULT_MIN_BET_LAMPORTS: u64 = 10_000_000;
+/// Default maximum bet (10 SOL)
+pub const DEFAULT_MAX_BET_LAMPORTS: u64 = 10_000_000_000;
+/// Default casino_vault float kept by `rebalance_vaults` (20 SOL)
+pub const DEFAULT_RESERVE_TARGET_LAMPORTS: u64 = 20_000_000_000;
+/// Upper bound for the casino_vault reserve target (1,000 SOL)
+pub const MAX_RESERVE_TARGET_LAMPORTS: u64 = 1_000_000_000_000;
+/// Upper bound for the treasury's share of swept profit (50%)
+pub const MAX_TREASURY_SHARE_BPS: u16 = 5_000;
+/// Upper bound for the protocol fee, as a share of the house edge (50%)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 5_000;
+/// Default cap on one bet's worst-case payout, as a share of available bankroll (5%)
+pub const DEFAULT_MAX_EXPOSURE_BPS: u16 = 500;
+
+/// Per-subsystem pause bits stored in `Admin.pause_flags`
+pub const PAUSE_GAMES: u8 = 1 << 0;
+pub const PAUSE_CLAIMS: u8 = 1 << 1;
+pub const PAUSE_LP_DEPOSIT: u8 = 1 << 2;
+pub const PAUSE_LP_WITHDRAW: u8 = 1 << 3;
+pub const PAUSE_QUESTS: u8 = 1 << 4;
+pub const PAUSE_ALL_FLAGS: u8 =
+    PAUSE_GAMES | PAUSE_CLAIMS | PAUSE_LP_DEPOSIT | PAUSE_LP_WITHDRAW | PAUSE_QUESTS;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Admin {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub casino_vault: Pubkey,
+    pub lp_vault: Pubkey,
+    /// Default house edge for games created by `init_game_config`; live games use their
+    /// `GameConfig`
+    pub house_edge_bps: u16,
+    /// Flat fee every quest creator pays the treasury
+    pub quest_creation_fee: u64,
+    /// Default bet limits for games created by `init_game_config`
+    pub min_bet: u64,
+    pub max_bet: u64,
+    /// Key allowed to toggle pauses alongside the authority
+    pub guardian: Pubkey,
+    /// Global circuit breaker: stops every subsystem when set
+    pub paused: bool,
+    /// Bitmask of PAUSE_* subsystem flags
+    pub pause_flags: u8,
+    /// Lamports wagered into casino_vault across all games
+    pub total_wagered: u64,
+    /// Lamports paid to winners out of casino_vault
+    pub total_paid_out: u64,
+    /// Realized profit already distributed to the LP vault and treasury
+    pub total_profit_swept: u64,
+    /// casino_vault balance `rebalance_vaults` keeps on hand for payouts
+    pub reserve_target: u64,
+    /// Share of swept profit paid to the treasury instead of LPs
+    pub treasury_share_bps: u16,
+    /// LP capital moved into casino_vault to restore the reserve, repaid before profit
+    pub lp_float_lent: u64,
+    /// Protocol fee per wager, in bps of the game's house edge
+    pub protocol_fee_bps: u16,
+    /// Protocol fees held in casino_vault awaiting `withdraw_treasury`
+    pub treasury_accrued: u64,
+    /// All protocol fees ever accrued
+    pub total_protocol_fees: u64,
+    /// Cap on a bet's worst-case payout, in bps of bankroll not already owed to open games
+    pub max_exposure_bps: u16,
+    pub bump: u8,
+}
+
+/// Admin layout written before the accounting, pause and exposure fields were added.
+/// Only read by `migrate_admin` to upgrade accounts created by earlier deployments.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct AdminV0 {
     pub authority: Pubkey,
     pub treasury: Pubkey,
     pub casino_vault: Pubkey,
@@ -12,4 +91,103 @@ pub struct Admin {
     pub house_edge_bps: u16,
     pub quest_creation_fee: u64,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+impl Admin {
+    /// A fresh admin for `authority` with every setting at its default
+    pub fn new(authority: Pubkey, casino_vault: Pubkey, lp_vault: Pubkey, bump: u8) -> Self {
+        Admin {
+            authority,
+            treasury: authority,
+            casino_vault,
+            lp_vault,
+            house_edge_bps: DEFAULT_HOUSE_EDGE_BPS,
+            quest_creation_fee: DEFAULT_QUEST_CREATION_FEE,
+            min_bet: DEFAULT_MIN_BET_LAMPORTS,
+            max_bet: DEFAULT_MAX_BET_LAMPORTS,
+            guardian: authority,
+            paused: false,
+            pause_flags: 0,
+            total_wagered: 0,
+            total_paid_out: 0,
+            total_profit_swept: 0,
+            reserve_target: DEFAULT_RESERVE_TARGET_LAMPORTS,
+            treasury_share_bps: 0,
+            lp_float_lent: 0,
+            protocol_fee_bps: 0,
+            treasury_accrued: 0,
+            total_protocol_fees: 0,
+            max_exposure_bps: DEFAULT_MAX_EXPOSURE_BPS,
+            bump,
+        }
+    }
+
+    /// True if the global breaker or the given subsystem flag is set
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused || self.pause_flags & flag != 0
+    }
+
+    pub fn can_pause(&self, key: &Pubkey) -> bool {
+        *key == self.authority || *key == self.guardian
+    }
+
+    /// Count a stake received by casino_vault and accrue the protocol's share of its edge
+    pub fn record_wager(&mut self, amount: u64, house_edge_bps: u16) -> Result<()> {
+        self.total_wagered = self.total_wagered.checked_add(amount).ok_or(CasinoError::Overflow)?;
+
+        let fee = protocol_fee(amount, house_edge_bps, self.protocol_fee_bps)?;
+        self.treasury_accrued =
+            self.treasury_accrued.checked_add(fee).ok_or(CasinoError::Overflow)?;
+        self.total_protocol_fees = self
+            .total_protocol_fees
+            .checked_add(fee)
+            .ok_or(CasinoError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        self.total_paid_out = self.total_paid_out.checked_add(amount).ok_or(CasinoError::Overflow)?;
+        Ok(())
+    }
+
+    /// Realized house profit (wagers kept minus payouts and protocol fees) not yet swept.
+    /// Zero while cumulative payouts, fees and past sweeps exceed cumulative wagers, so
+    /// losses are made back before any more profit is swept.
+    pub fn unswept_profit(&self) -> u64 {
+        self.total_wagered
+            .saturating_sub(self.total_paid_out)
+            .saturating_sub(self.total_protocol_fees)
+            .saturating_sub(self.total_profit_swept)
+    }
+
+    /// Lamports a quest that can mint `faith_reward` FAITH up to `max_rewards` times pays
+    /// into the promo budget: all of that FAITH at its free-bet value
+    pub fn quest_faith_backing(faith_reward: u64, max_rewards: u32) -> Result<u64> {
+        faith_reward
+            .checked_mul(max_rewards as u64)
+            .and_then(|faith| faith.checked_mul(FREE_BET_LAMPORTS_PER_FAITH))
+            .ok_or(CasinoError::Overflow.into())
+    }
+
+    /// casino_vault lamports that are house float, i.e. not rent and not owed to the treasury
+    pub fn casino_float(&self, casino_vault_lamports: u64) -> Result<u64> {
+        let reserve = Rent::get()?.minimum_balance(0);
+        Ok(casino_vault_lamports
+            .saturating_sub(reserve)
+            .saturating_sub(self.treasury_accrued))
+    }
+
+    /// Part of `lp_float_lent` still held as casino float. Payouts can eat into the float,
+    /// and LPs are never owed more of it than casino_vault can actually repay.
+    pub fn lp_float_backed(&self, casino_vault_lamports: u64) -> Result<u64> {
+        Ok(self.lp_float_lent.min(self.casino_float(casino_vault_lamports)?))
+    }
+
+    /// Lamports available to pay winners: casino float plus LP capital above rent
+    pub fn bankroll(&self, casino_vault_lamports: u64, lp_vault_lamports: u64) -> Result<u64> {
+        let reserve = Rent::get()?.minimum_balance(0);
+        self.casino_float(casino_vault_lamports)?
+            .checked_add(lp_vault_lamports.saturating_sub(reserve))
+            .ok_or(CasinoError::Overflow.into())
+    }
+}
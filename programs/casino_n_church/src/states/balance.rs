@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// A casino balance held as an encrypted handle. Stakes and payouts moved through it stay
+/// hidden; only deposits and withdrawals reveal amounts.
+/// Player PDA: ["encrypted_balance", admin, owner]
+/// The house side of confidential games: ["house_balance", admin], owned by the authority
+/// Lamports backing every balance sit in the ["balance_vault", admin] PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct EncryptedBalance {
+    pub owner: Pubkey,
+    pub admin: Pubkey,
+    /// Euint128 handle of the balance in lamports
+    pub handle: u128,
+    /// Lamports of an in-flight withdrawal, debited only if the balance covered them
+    pub pending_withdraw: u64,
+    /// Ebool handle of whether the balance covered `pending_withdraw`
+    pub pending_handle: u128,
+    pub bump: u8,
+}
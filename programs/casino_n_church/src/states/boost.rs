@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CasinoError;
+use crate::payout_math::BPS;
+use crate::states::MAX_HOUSE_EDGE_BPS;
+
+/// Free bet credit bought with one FAITH (1,000 FAITH buys a 0.1 SOL free bet)
+pub const FREE_BET_LAMPORTS_PER_FAITH: u64 = 100_000;
+/// FAITH per basis point taken off the house edge
+pub const FAITH_PER_EDGE_DISCOUNT_BPS: u64 = 1;
+/// FAITH per basis point added to the payout
+pub const FAITH_PER_MULTIPLIER_BPS: u64 = 2;
+/// Largest payout bonus a voucher can carry (+50%)
+pub const MAX_BOOST_MULTIPLIER_BPS: u64 = 5_000;
+/// Stake an edge discount or payout multiplier is priced per: the per-bps FAITH rates above
+/// buy the boost on up to this much stake, and larger stake caps cost proportionally more
+pub const BOOST_STAKE_UNIT_LAMPORTS: u64 = 1_000_000_000;
+
+/// What a Boost voucher does to the bet that consumes it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BoostKind {
+    /// The stake is paid from promo credit escrowed in the voucher; value is the credit in lamports
+    FreeBet,
+    /// value basis points come off the game's house edge
+    EdgeDiscount,
+    /// Payouts are computed on the stake scaled up by (BPS + value) / BPS
+    PayoutMultiplier,
+}
+
+impl BoostKind {
+    /// FAITH burned to redeem a voucher of `value` usable on stakes up to `max_stake`, or None
+    /// if either is out of range. A free bet's stake is its credit, so `max_stake` must match.
+    pub fn faith_cost(&self, value: u64, max_stake: u64) -> Option<u64> {
+        if value == 0 || max_stake == 0 {
+            return None;
+        }
+        let stake_units = max_stake.div_ceil(BOOST_STAKE_UNIT_LAMPORTS);
+        match self {
+            BoostKind::FreeBet if max_stake == value => {
+                Some(value.div_ceil(FREE_BET_LAMPORTS_PER_FAITH))
+            }
+            BoostKind::EdgeDiscount if value <= MAX_HOUSE_EDGE_BPS as u64 => {
+                value.checked_mul(FAITH_PER_EDGE_DISCOUNT_BPS)?.checked_mul(stake_units)
+            }
+            BoostKind::PayoutMultiplier if value <= MAX_BOOST_MULTIPLIER_BPS => {
+                value.checked_mul(FAITH_PER_MULTIPLIER_BPS)?.checked_mul(stake_units)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A voucher minted by burning FAITH and consumed by the player's next boosted bet, or
+/// dropped with `cancel_boost`.
+/// A free bet's credit is held in this account's lamports until the bet is placed.
+/// PDA: ["boost", admin, owner]
+#[account]
+#[derive(InitSpace)]
+pub struct Boost {
+    pub owner: Pubkey,
+    pub admin: Pubkey,
+    pub kind: BoostKind,
+    pub value: u64,
+    /// Largest stake, in lamports, the voucher may boost
+    pub max_stake: u64,
+    pub faith_cost: u64,
+    pub bump: u8,
+}
+
+/// How a bet is staked and paid once its voucher, if any, is applied
+pub struct BoostTerms {
+    /// Part of the stake paid from the voucher's promo credit rather than by the player
+    pub promo_stake: u64,
+    pub house_edge_bps: u16,
+    /// Scale applied to the stake payouts are computed on (BPS = 1x)
+    pub payout_bps: u64,
+    /// Largest stake the bet may carry (u64::MAX without a voucher)
+    pub max_stake: u64,
+}
+
+impl BoostTerms {
+    /// Terms for a SOL bet of `amount` at `house_edge_bps`. The stake may not exceed the
+    /// voucher's `max_stake`, and a free bet must stake exactly its credit.
+    pub fn new(boost: Option<&Boost>, amount: u64, house_edge_bps: u16) -> Result<Self> {
+        let terms = Self::from_voucher(boost, house_edge_bps);
+        require!(amount <= terms.max_stake, CasinoError::InvalidBoost);
+        if boost.is_some_and(|boost| boost.kind == BoostKind::FreeBet) {
+            require!(amount == terms.promo_stake, CasinoError::InvalidBoost);
+        }
+        Ok(terms)
+    }
+
+    /// Terms for a hidden SOL stake, which the caller must cap at `max_stake` itself.
+    /// A free bet's credit can't be matched against a stake nobody can see.
+    pub fn confidential(boost: Option<&Boost>, house_edge_bps: u16) -> Result<Self> {
+        require!(
+            boost.is_none_or(|boost| boost.kind != BoostKind::FreeBet),
+            CasinoError::CurrencyMismatch
+        );
+        Ok(Self::from_voucher(boost, house_edge_bps))
+    }
+
+    /// Terms a voucher, if any, grants before checking them against the stake
+    fn from_voucher(boost: Option<&Boost>, house_edge_bps: u16) -> Self {
+        let mut terms = BoostTerms {
+            promo_stake: 0,
+            house_edge_bps,
+            payout_bps: BPS,
+            max_stake: u64::MAX,
+        };
+        let Some(boost) = boost else {
+            return terms;
+        };
+        terms.max_stake = boost.max_stake;
+        match boost.kind {
+            BoostKind::FreeBet => terms.promo_stake = boost.value,
+            BoostKind::EdgeDiscount => {
+                terms.house_edge_bps = house_edge_bps.saturating_sub(boost.value as u16);
+            }
+            BoostKind::PayoutMultiplier => terms.payout_bps = BPS + boost.value,
+        }
+        terms
+    }
+
+    /// Stake the player pays
+    pub fn player_stake(&self, amount: u64) -> u64 {
+        amount - self.promo_stake
+    }
+
+    /// Move a free bet's escrowed credit from its voucher into `vault`
+    pub fn release_promo_stake<'info>(
+        &self,
+        boost: &Option<Account<'info, Boost>>,
+        vault: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if let Some(boost) = boost {
+            if self.promo_stake > 0 {
+                boost.sub_lamports(self.promo_stake)?;
+                vault.add_lamports(self.promo_stake)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stake that payouts are computed on
+    pub fn payout_basis(&self, amount: u64) -> Result<u64> {
+        let basis = (amount as u128)
+            .checked_mul(self.payout_bps as u128)
+            .ok_or(CasinoError::Overflow)?
+            / BPS as u128;
+        u64::try_from(basis).map_err(|_| CasinoError::Overflow.into())
+    }
+}
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    errors::CasinoError,
+    payout_math::{slot_return_bps, BPS},
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct RouletteBet {
@@ -7,7 +12,7 @@ pub struct RouletteBet {
     pub seed: u128,
     pub slot: u64,
     pub amount: u64,
-    /// Bet type: 0 = straight, 1 = color (0 red, 1 black)
+    /// Bet type: one of the ROULETTE_BET_* constants
     pub bet_type: u8,
     pub choice: u8,
     pub bump: u8,
@@ -24,6 +29,51 @@ impl RouletteBet {
     }
 }
 
+/// Roulette bet types and how the encrypted choice is read for each
+/// Straight: number 0-36 (pays straight_multiplier, 36x by default)
+pub const ROULETTE_BET_STRAIGHT: u8 = 0;
+/// Color: 0 = red, 1 = black (2x)
+pub const ROULETTE_BET_COLOR: u8 = 1;
+/// Parity: 0 = even, 1 = odd (2x)
+pub const ROULETTE_BET_ODD_EVEN: u8 = 2;
+/// Range: 0 = low (1-18), 1 = high (19-36) (2x)
+pub const ROULETTE_BET_HIGH_LOW: u8 = 3;
+/// Dozen: 0 = 1-12, 1 = 13-24, 2 = 25-36 (3x)
+pub const ROULETTE_BET_DOZEN: u8 = 4;
+/// Column: 0 = 1,4..34, 1 = 2,5..35, 2 = 3,6..36 (3x)
+pub const ROULETTE_BET_COLUMN: u8 = 5;
+/// Split: two numbers encoded as first * 37 + second (18x)
+pub const ROULETTE_BET_SPLIT: u8 = 6;
+/// Recorded in `GameType::Roulette` for multi-leg spins (legs live in `RouletteSpinLegs`)
+pub const ROULETTE_BET_MULTI: u8 = u8::MAX;
+
+pub const ROULETTE_EVEN_MONEY_MULTIPLIER: u64 = 2;
+pub const ROULETTE_DOZEN_COLUMN_MULTIPLIER: u64 = 3;
+pub const ROULETTE_SPLIT_MULTIPLIER: u64 = 18;
+
+/// Most legs a single multi-bet spin may carry (bounded by compute)
+pub const MAX_ROULETTE_LEGS: usize = 4;
+
+/// One wager on a multi-bet roulette spin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct RouletteLeg {
+    pub bet_type: u8,
+    pub amount: u64,
+    pub choice_handle: u128,
+    pub payout_handle: u128,
+}
+
+/// Companion to a multi-bet roulette `GameResult` recording each leg
+/// PDA: ["roulette_legs", game]
+#[account]
+#[derive(InitSpace)]
+pub struct RouletteSpinLegs {
+    pub game: Pubkey,
+    #[max_len(MAX_ROULETTE_LEGS)]
+    pub legs: Vec<RouletteLeg>,
+    pub bump: u8,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct CoinflipBet {
@@ -99,16 +149,116 @@ impl AviatorBet {
 /// Game type enum for unified GameResult
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum GameType {
-    /// Roulette: bet_type 0=straight (36x), 1=color (2x)
+    /// Roulette: bet_type is one of the ROULETTE_BET_* constants
     Roulette { bet_type: u8 },
     /// Coinflip: 50/50 heads or tails
     Coinflip,
     /// Slot: 3 reels with tiered payouts
     Slot,
     /// Aviator: cashout before crash
+    /// target_multiplier_bps is the public cap the encrypted target was bounded by
     Aviator { target_multiplier_bps: u32 },
 }
 
+/// Game ids used to seed per-game `GameConfig` PDAs (match `GameType` variant order)
+pub const GAME_ID_ROULETTE: u8 = 0;
+pub const GAME_ID_COINFLIP: u8 = 1;
+pub const GAME_ID_SLOT: u8 = 2;
+pub const GAME_ID_AVIATOR: u8 = 3;
+
+impl GameType {
+    pub fn id(&self) -> u8 {
+        match self {
+            GameType::Roulette { .. } => GAME_ID_ROULETTE,
+            GameType::Coinflip => GAME_ID_COINFLIP,
+            GameType::Slot => GAME_ID_SLOT,
+            GameType::Aviator { .. } => GAME_ID_AVIATOR,
+        }
+    }
+}
+
+// =============================================================================
+// Per-game configuration (limits, edge, multipliers, pause)
+// =============================================================================
+
+pub const DEFAULT_ROULETTE_STRAIGHT_MULTIPLIER: u64 = 36; // 35:1 + original bet
+pub const DEFAULT_SLOT_JACKPOT_MULTIPLIER: u64 = 60;
+pub const DEFAULT_SLOT_SMALL_WIN_MULTIPLIER: u64 = 2; // with the jackpot, 96% return before edge
+pub const DEFAULT_AVIATOR_MAX_MULTIPLIER_BPS: u64 = 100_000; // 10x
+
+/// Straight bets never pay more than the 37-pocket wheel allows
+pub const MAX_ROULETTE_STRAIGHT_MULTIPLIER: u64 = 36;
+pub const MAX_SLOT_JACKPOT_MULTIPLIER: u64 = 100;
+pub const MAX_AVIATOR_MULTIPLIER_BPS: u64 = 1_000_000; // 100x
+/// Lowest aviator cashout target a player may pick (1.01x)
+pub const MIN_AVIATOR_TARGET_BPS: u64 = 10_100;
+
+/// Per-game settings enforced by each `play_*` instruction
+/// PDA: ["game_config", admin, game_id]
+#[account]
+#[derive(InitSpace)]
+pub struct GameConfig {
+    pub admin: Pubkey,
+    /// One of the GAME_ID_* constants
+    pub game_id: u8,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub house_edge_bps: u16,
+    /// Roulette straight bet multiplier (0 for other games)
+    pub straight_multiplier: u64,
+    /// Slot three-of-a-kind multiplier (0 for other games)
+    pub jackpot_multiplier: u64,
+    /// Slot two-of-a-kind multiplier (0 for other games)
+    pub small_win_multiplier: u64,
+    /// Aviator crash ceiling in basis points (0 for other games)
+    pub max_multiplier_bps: u64,
+    /// When set, new bets on this game are rejected
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl GameConfig {
+    /// Check limits and multipliers are sane for this game
+    pub fn validate(&self, max_house_edge_bps: u16) -> Result<()> {
+        require!(self.min_bet > 0 && self.min_bet <= self.max_bet, CasinoError::InvalidConfig);
+        require!(self.house_edge_bps <= max_house_edge_bps, CasinoError::InvalidConfig);
+        match self.game_id {
+            GAME_ID_ROULETTE => require!(
+                self.straight_multiplier > 0
+                    && self.straight_multiplier <= MAX_ROULETTE_STRAIGHT_MULTIPLIER,
+                CasinoError::InvalidConfig
+            ),
+            GAME_ID_COINFLIP => {}
+            GAME_ID_SLOT => {
+                require!(
+                    self.small_win_multiplier > 0
+                        && self.small_win_multiplier <= self.jackpot_multiplier
+                        && self.jackpot_multiplier <= MAX_SLOT_JACKPOT_MULTIPLIER,
+                    CasinoError::InvalidConfig
+                );
+                // The edge only scales payouts, so the reel odds themselves must favour the house
+                let rtp = slot_return_bps(
+                    self.jackpot_multiplier,
+                    self.small_win_multiplier,
+                    self.house_edge_bps,
+                )?;
+                require!(rtp < BPS - self.house_edge_bps as u64, CasinoError::InvalidConfig);
+            }
+            GAME_ID_AVIATOR => require!(
+                self.max_multiplier_bps > BPS
+                    && self.max_multiplier_bps <= MAX_AVIATOR_MULTIPLIER_BPS,
+                CasinoError::InvalidConfig
+            ),
+            _ => return err!(CasinoError::InvalidGameId),
+        }
+        Ok(())
+    }
+}
+
+/// Slots after a game is played before anyone may close it as forfeited (~7 days), so
+/// abandoned games stop holding their `max_payout` against the bankroll
+pub const GAME_EXPIRY_SLOTS: u64 = 1_512_000;
+
 /// Unified game result storing encrypted handles
 /// Used for all casino games with Inco privacy integration
 #[account]
@@ -116,11 +266,17 @@ pub enum GameType {
 pub struct GameResult {
     /// Player who placed the bet
     pub player: Pubkey,
+    /// Casino (Admin PDA) whose vaults and exposure back this game
+    pub admin: Pubkey,
+    /// Mint the bet and payout are denominated in; `Pubkey::default()` for native SOL
+    pub mint: Pubkey,
+    /// Stake and payout are encrypted and settle through `EncryptedBalance`s, not a vault
+    pub confidential: bool,
     /// Type of game played
     pub game_type: GameType,
     /// Unique seed for this game
     pub seed: u128,
-    /// Amount bet in lamports
+    /// Amount bet in lamports (or base units of `mint`)
     pub bet_amount: u64,
     /// Slot when bet was placed
     pub slot: u64,
@@ -131,6 +287,9 @@ pub struct GameResult {
     /// Encrypted payout amount (0 if lost, winnings if won)
     pub payout_handle: u128,
     
+    /// Largest payout this game can produce, held in `Exposure` until it is resolved
+    pub max_payout: u64,
+    
     /// Array of random result handles - supports up to 4 random values
     /// [0] = primary result (coinflip flip, roulette spin, aviator crash, slot reel1)
     /// [1] = slot reel2 (0 for other games)
@@ -142,4 +301,16 @@ pub struct GameResult {
     pub claimed: bool,
     /// PDA bump
     pub bump: u8,
-}
\ No newline at end of file
+}
+impl GameResult {
+    /// Multi-bet roulette games keep their legs in a separate `RouletteSpinLegs` PDA
+    pub fn has_roulette_legs(&self) -> bool {
+        self.game_type == GameType::Roulette { bet_type: ROULETTE_BET_MULTI }
+    }
+
+    /// Unclaimed for `GAME_EXPIRY_SLOTS`; the player's winnings are forfeit to anyone
+    /// who closes it
+    pub fn is_expired(&self, now_slot: u64) -> bool {
+        now_slot >= self.slot.saturating_add(GAME_EXPIRY_SLOTS)
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+/// Longest prompt or sermon URI stored on-chain
+pub const MAX_QUEST_URI_LEN: usize = 200;
+/// Most FAITH a single approved sermon may earn
+pub const MAX_WRITING_QUEST_FAITH: u64 = 1_000;
+
+/// A sermon-writing prompt whose curator rewards approved submissions with FAITH
+/// PDA: ["writing_quest", admin, creator, quest_id]
+#[account]
+#[derive(InitSpace)]
+pub struct WritingQuest {
+    pub admin: Pubkey,
+    pub creator: Pubkey,
+    /// Judge allowed to approve submissions
+    pub curator: Pubkey,
+    pub quest_id: u64,
+    /// Hash of the prompt document at `prompt_uri`
+    pub prompt_hash: [u8; 32],
+    #[max_len(MAX_QUEST_URI_LEN)]
+    pub prompt_uri: String,
+    /// FAITH credited to the author of each approved sermon
+    pub faith_reward: u64,
+    /// Last slot sermons may be submitted; approvals may follow later
+    pub deadline_slot: u64,
+    pub max_approvals: u32,
+    pub submissions: u32,
+    pub approvals: u32,
+    pub bump: u8,
+}
+
+/// One author's sermon for a writing quest; the content lives off-chain at `uri`
+/// PDA: ["sermon", quest, author]
+#[account]
+#[derive(InitSpace)]
+pub struct SermonSubmission {
+    pub quest: Pubkey,
+    pub author: Pubkey,
+    /// Hash of the sermon at `uri`, so the approved text can't be swapped later
+    pub content_hash: [u8; 32],
+    #[max_len(MAX_QUEST_URI_LEN)]
+    pub uri: String,
+    pub submitted_slot: u64,
+    pub approved: bool,
+    pub bump: u8,
+}
+
+/// Most FAITH a single blink quest completion may earn
+pub const MAX_BLINK_QUEST_FAITH: u64 = 1_000;
+
+/// A social task shared as a Blink, completed with an attestation from `verifier`
+/// PDA: ["blink_quest", admin, creator, quest_id]
+/// Lamport bounties are escrowed in the account itself until completed or closed.
+#[account]
+#[derive(InitSpace)]
+pub struct BlinkQuest {
+    pub admin: Pubkey,
+    pub creator: Pubkey,
+    /// Key whose Ed25519 signature over `attestation_message` proves a completion; must
+    /// have an enabled `QuestVerifier`
+    pub verifier: Pubkey,
+    pub quest_id: u64,
+    /// Blink action URL describing the task
+    #[max_len(MAX_QUEST_URI_LEN)]
+    pub uri: String,
+    /// FAITH credited per completion
+    pub faith_reward: u64,
+    /// Lamports paid per completion (0 for FAITH only)
+    pub bounty: u64,
+    /// Escrowed bounty lamports not yet paid out
+    pub reward_pool: u64,
+    /// Last slot a completion may be recorded
+    pub deadline_slot: u64,
+    pub max_completions: u32,
+    pub completions: u32,
+    pub bump: u8,
+}
+
+impl BlinkQuest {
+    /// What the verifier signs to attest `completer` finished `quest`
+    pub fn attestation_message(quest: &Pubkey, completer: &Pubkey) -> Vec<u8> {
+        let mut s = b"blink_quest".to_vec();
+        s.extend_from_slice(quest.as_ref());
+        s.extend_from_slice(completer.as_ref());
+        s
+    }
+}
+
+/// A verifier key the admin authority trusts to attest blink quest completions.
+/// Quests may only name an enabled verifier, and disabling one stops its attestations.
+/// PDA: ["quest_verifier", admin, verifier]
+#[account]
+#[derive(InitSpace)]
+pub struct QuestVerifier {
+    pub admin: Pubkey,
+    pub verifier: Pubkey,
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+/// Marks that `completer` has been rewarded for a blink quest
+/// PDA: ["blink_completion", quest, completer]
+#[account]
+#[derive(InitSpace)]
+pub struct BlinkCompletion {
+    pub quest: Pubkey,
+    pub completer: Pubkey,
+    pub slot: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CasinoError;
+use crate::payout_math::exposure_limit;
+
+/// Worst-case payouts of games that have been played but not yet claimed or closed
+/// PDA: ["exposure", admin]
+#[account]
+#[derive(InitSpace)]
+pub struct Exposure {
+    pub admin: Pubkey,
+    /// Sum of `GameResult.max_payout` over unresolved games
+    pub outstanding: u64,
+    /// Number of unresolved games
+    pub open_games: u64,
+    pub bump: u8,
+}
+
+impl Exposure {
+    /// Take on a new game's worst-case payout, if it fits within `max_exposure_bps` of
+    /// the bankroll not already promised to other open games
+    pub fn reserve(&mut self, max_payout: u64, bankroll: u64, max_exposure_bps: u16) -> Result<()> {
+        require!(
+            max_payout <= exposure_limit(self.headroom(bankroll), max_exposure_bps),
+            CasinoError::ExposureLimitExceeded
+        );
+
+        self.outstanding = self.outstanding.checked_add(max_payout).ok_or(CasinoError::Overflow)?;
+        self.open_games = self.open_games.checked_add(1).ok_or(CasinoError::Overflow)?;
+        Ok(())
+    }
+
+    /// Bankroll not promised to open games; LP exits and profit sweeps may not go below it
+    pub fn headroom(&self, bankroll: u64) -> u64 {
+        bankroll.saturating_sub(self.outstanding)
+    }
+
+    /// Drop a resolved game's worst-case payout
+    pub fn release(&mut self, max_payout: u64) {
+        self.outstanding = self.outstanding.saturating_sub(max_payout);
+        self.open_games = self.open_games.saturating_sub(1);
+    }
+}
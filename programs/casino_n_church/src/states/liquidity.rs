@@ -1,28 +1,91 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::CasinoError;
+
+/// LP share tokens use the same precision as SOL
+pub const LP_MINT_DECIMALS: u8 = 9;
+/// Minimum slots between profit distributions by `rebalance_vaults` (~1 day)
+pub const SWEEP_EPOCH_SLOTS: u64 = 216_000;
+/// Default slots between `request_withdraw` and `execute_withdraw` (~1 hour)
+pub const DEFAULT_WITHDRAW_DELAY_SLOTS: u64 = 9_000;
+/// Upper bound for the withdrawal delay (~1 week)
+pub const MAX_WITHDRAW_DELAY_SLOTS: u64 = 1_512_000;
+
 #[account]
 #[derive(InitSpace)]
 pub struct LiquidityPool {
     pub admin: Pubkey,
+    /// SPL mint of pool shares, PDA ["lp_mint", admin]
     pub lp_mint: Pubkey,
     pub vault: Pubkey,
+    /// Lamports contributed by LPs, net of redeemed principal
     pub total_deposits: u64,
-    pub yield_bps: u16,
+    /// Slot of the last profit distribution; LP yield comes only from these sweeps
+    pub last_sweep_slot: u64,
+    /// Slots a withdraw request must wait before it can be executed
+    pub withdraw_delay_slots: u64,
+    /// Id of the oldest unfilled `WithdrawRequest`; only it may be executed
+    pub queue_head: u64,
+    /// Id the next `WithdrawRequest` will take
+    pub queue_tail: u64,
+    /// Shares held in the ["lp_escrow", admin] account for queued requests
+    pub queued_shares: u64,
+    /// Cost basis carried by queued requests.
+    /// Invariant: sum of `LiquidityProvider.balance` + queued_principal == total_deposits
+    pub queued_principal: u64,
+    /// Shares priced into NAV that no LP holds, backing lamports that were in the vault
+    /// while no LP shares existed (the `initialize_vault` seed, or what the last LP left)
+    pub unowned_shares: u64,
     pub bump: u8,
     pub vault_bump: u8,
     pub mint_bump: u8,
+    /// Bump of the ["lp_mint_authority", admin] PDA that signs share mints
     pub mint_auth_bump: u8,
 }
 
+impl LiquidityPool {
+    /// Share supply NAV is priced over: LP shares plus `unowned_shares`. While no LP holds
+    /// shares, whatever the vault holds is re-priced into unowned shares at one per lamport,
+    /// so the next depositor can't claim lamports they didn't put in.
+    pub fn priced_supply(&mut self, lp_supply: u64, pool_assets: u64) -> Result<u64> {
+        if lp_supply == 0 {
+            self.unowned_shares = pool_assets;
+        }
+        lp_supply
+            .checked_add(self.unowned_shares)
+            .ok_or(CasinoError::Overflow.into())
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct LiquidityProvider {
     pub owner: Pubkey,
     pub admin: Pubkey,
+    /// Lamports contributed (cost basis); shares are held as LP tokens
     pub balance: u64,
     pub deposit_slot: u64,
     pub last_withdraw_slot: u64,
-    pub accrued_yield: u64,
     pub bump: u8,
 }
 
+
+/// A queued LP redemption. Shares are escrowed at request time and priced at NAV when
+/// executed, so a pending loss is shared with the LPs who stay.
+/// PDA: ["withdraw_request", admin, id]
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawRequest {
+    pub owner: Pubkey,
+    pub admin: Pubkey,
+    /// Position in the pool's redemption queue
+    pub id: u64,
+    /// Escrowed shares not yet redeemed
+    pub shares: u64,
+    /// Cost basis carried by those shares
+    pub principal: u64,
+    /// If false, the request waits at the head until liquidity can fill it in one go
+    pub allow_partial: bool,
+    pub request_slot: u64,
+    pub bump: u8,
+}
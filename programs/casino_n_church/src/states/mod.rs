@@ -1,9 +1,20 @@
 pub mod admin;
+pub mod balance;
+pub mod boost;
 pub mod casino;
 pub mod church;
+pub mod exposure;
 pub mod liquidity;
+pub mod profile;
+pub mod token;
 
 pub use admin::*;
+pub use balance::*;
+pub use boost::*;
 pub use casino::*;
+pub use church::*;
+pub use exposure::*;
 pub use liquidity::*;
+pub use profile::*;
+pub use token::*;
 
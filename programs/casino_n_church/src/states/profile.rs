@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CasinoError;
+use crate::realm_math::settle_decay;
+
+/// LUCK for every bet placed
+pub const LUCK_PER_PLAY: u64 = 1;
+/// Extra LUCK for every winning claim
+pub const LUCK_PER_WIN: u64 = 5;
+/// Slots a realm can be left alone before the other realm's points start to decay (~1 day)
+pub const REALM_GRACE_SLOTS: u64 = 216_000;
+/// Slots per decay step once the grace period is over (~1 day)
+pub const REALM_DECAY_PERIOD_SLOTS: u64 = 216_000;
+/// Share of the balance lost per decay step (10%)
+pub const REALM_DECAY_BPS: u64 = 1_000;
+
+/// A player's standing in the Casino and the Church, created on their first bet or quest
+/// PDA: ["player_profile", admin, owner]
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub owner: Pubkey,
+    pub admin: Pubkey,
+    /// LUCK earned in the Casino; decays while the Church is neglected
+    pub luck: u64,
+    pub last_casino_slot: u64,
+    pub last_church_slot: u64,
+    /// Slots up to which decay has been charged
+    pub luck_settled_slot: u64,
+    pub faith_settled_slot: u64,
+    /// FAITH in the owner's FAITH token account when the program last saw it, less decay
+    /// charged since. FAITH itself is an SPL token (see `faith_token`); this is what decays
+    /// while the Casino is neglected, so FAITH moved in or out meanwhile doesn't change it.
+    pub faith_held: u64,
+    /// FAITH decay charged but not yet burned from the owner or netted against a reward
+    pub faith_decay_owed: u64,
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    /// Fill in a profile created by init_if_needed; no-op once it has an owner.
+    /// Both realms count as visited now, so decay never reaches back to slot 0.
+    pub fn open(&mut self, owner: Pubkey, admin: Pubkey, bump: u8, now_slot: u64) {
+        if self.owner != Pubkey::default() {
+            return;
+        }
+        self.owner = owner;
+        self.admin = admin;
+        self.bump = bump;
+        self.last_casino_slot = now_slot;
+        self.last_church_slot = now_slot;
+        self.luck_settled_slot = now_slot;
+        self.faith_settled_slot = now_slot;
+    }
+
+    /// Charge the decay owed up to `now_slot`: LUCK directly, FAITH on `faith_held` as a
+    /// debt to burn or net later
+    pub fn settle(&mut self, now_slot: u64) {
+        (self.luck, self.luck_settled_slot) =
+            settle_decay(self.luck, self.last_church_slot, self.luck_settled_slot, now_slot);
+        let (faith_held, faith_settled_slot) =
+            settle_decay(self.faith_held, self.last_casino_slot, self.faith_settled_slot, now_slot);
+        self.faith_decay_owed = self.faith_decay_owed.saturating_add(self.faith_held - faith_held);
+        (self.faith_held, self.faith_settled_slot) = (faith_held, faith_settled_slot);
+    }
+
+    /// A bet or claim: settle decay, add LUCK and restart the FAITH grace period
+    pub fn record_casino(&mut self, now_slot: u64, luck: u64) -> Result<()> {
+        self.settle(now_slot);
+        self.luck = self.luck.checked_add(luck).ok_or(CasinoError::Overflow)?;
+        self.last_casino_slot = now_slot;
+        Ok(())
+    }
+
+    /// A completed quest: settle decay and restart the LUCK grace period
+    pub fn record_church(&mut self, now_slot: u64) {
+        self.settle(now_slot);
+        self.last_church_slot = now_slot;
+    }
+
+    /// Decay owed up to `now_slot` that `available` FAITH pays off now (burned from the
+    /// owner's balance or withheld from a reward); any shortfall stays owed
+    pub fn take_faith_decay(&mut self, now_slot: u64, available: u64) -> u64 {
+        self.settle(now_slot);
+        let paid = self.faith_decay_owed.min(available);
+        self.faith_decay_owed -= paid;
+        paid
+    }
+
+    /// Start charging decay on the owner's FAITH balance as the program leaves it
+    pub fn sync_faith(&mut self, balance: u64) {
+        self.faith_held = balance;
+    }
+}
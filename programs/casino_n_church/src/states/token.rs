@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CasinoError;
+use crate::payout_math::exposure_limit;
+
+/// An SPL mint whitelisted for betting, with its own limits, vault and open liability
+/// PDA: ["mint_config", admin, mint]
+/// Its vault is the token account PDA ["token_vault", admin, mint], owned by this config
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Bet limits in the mint's base units
+    pub min_bet: u64,
+    pub max_bet: u64,
+    /// Disabled mints take no new bets; their open games can still be claimed
+    pub enabled: bool,
+    /// Sum of `GameResult.max_payout` over unresolved games in this mint
+    pub outstanding: u64,
+    /// Number of unresolved games in this mint
+    pub open_games: u64,
+    pub total_wagered: u64,
+    pub total_paid_out: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl MintConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.min_bet > 0 && self.min_bet <= self.max_bet, CasinoError::InvalidConfig);
+        Ok(())
+    }
+
+    /// Take on a new game's worst-case payout, if it fits within `max_exposure_bps` of
+    /// the vault balance not already promised to other open games in this mint
+    pub fn reserve(
+        &mut self,
+        max_payout: u64,
+        vault_balance: u64,
+        max_exposure_bps: u16,
+    ) -> Result<()> {
+        let headroom = vault_balance.saturating_sub(self.outstanding);
+        require!(
+            max_payout <= exposure_limit(headroom, max_exposure_bps),
+            CasinoError::ExposureLimitExceeded
+        );
+
+        self.outstanding = self.outstanding.checked_add(max_payout).ok_or(CasinoError::Overflow)?;
+        self.open_games = self.open_games.checked_add(1).ok_or(CasinoError::Overflow)?;
+        Ok(())
+    }
+
+    /// Drop a resolved game's worst-case payout
+    pub fn release(&mut self, max_payout: u64) {
+        self.outstanding = self.outstanding.saturating_sub(max_payout);
+        self.open_games = self.open_games.saturating_sub(1);
+    }
+
+    pub fn record_wager(&mut self, amount: u64) -> Result<()> {
+        self.total_wagered = self.total_wagered.checked_add(amount).ok_or(CasinoError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        self.total_paid_out = self.total_paid_out.checked_add(amount).ok_or(CasinoError::Overflow)?;
+        Ok(())
+    }
+}
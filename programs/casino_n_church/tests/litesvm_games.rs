@@ -0,0 +1,2723 @@
+//! In-process tests against the built program, with the mock Inco Lightning program
+//! (tests/fixtures/mock_inco_lightning) deployed at the Inco program id. Every handle the
+//! mock returns is its own plaintext, so payouts can be checked exactly.
+//!
+//! Build both programs first: `anchor build` and `yarn build:mock-inco`, then
+//! `cargo test -p casino_n_church`.
+use std::path::PathBuf;
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program,
+    AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address, get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account,
+    },
+    token::{
+        spl_token::{
+            self,
+            solana_program::{program_option::COption, program_pack::Pack},
+        },
+        Mint, TokenAccount,
+    },
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+};
+use casino_n_church::{
+    calculate_aviator_payout, calculate_win_payout, decay_points, lamports_for_shares, lp_exposure,
+    protocol_fee, roulette_bet_wins, roulette_multiplier, shares_for_deposit, Admin, AdminV0,
+    BlinkQuest, BlinkQuestParams, Boost, BoostKind, CasinoError, EncryptedBalance, Exposure,
+    GameConfig, GameConfigParams, GameResult, GameType, LiquidityPool, LiquidityProvider,
+    MintConfig, PayoutProof, PlayerProfile, RouletteLegInput, RouletteSpinLegs, SermonSubmission,
+    WithdrawRequest, WritingQuest, WritingQuestParams, BOOST_STAKE_UNIT_LAMPORTS, BPS,
+    DEFAULT_HOUSE_EDGE_BPS, DEFAULT_MAX_BET_LAMPORTS, DEFAULT_MAX_EXPOSURE_BPS,
+    DEFAULT_MIN_BET_LAMPORTS, DEFAULT_RESERVE_TARGET_LAMPORTS, DEFAULT_SLOT_JACKPOT_MULTIPLIER,
+    DEFAULT_SLOT_SMALL_WIN_MULTIPLIER, DEFAULT_WITHDRAW_DELAY_SLOTS, FAITH_DECIMALS,
+    FAITH_PER_EDGE_DISCOUNT_BPS, FAITH_PER_MULTIPLIER_BPS, FREE_BET_LAMPORTS_PER_FAITH,
+    GAME_EXPIRY_SLOTS, GAME_ID_AVIATOR, GAME_ID_COINFLIP, GAME_ID_ROULETTE, GAME_ID_SLOT,
+    LUCK_PER_PLAY, LUCK_PER_WIN, MAX_BOOST_MULTIPLIER_BPS, MAX_RESERVE_TARGET_LAMPORTS,
+    MAX_WRITING_QUEST_FAITH, PAUSE_GAMES, REALM_DECAY_PERIOD_SLOTS, REALM_GRACE_SLOTS,
+    ROULETTE_BET_COLOR, ROULETTE_BET_DOZEN, ROULETTE_BET_SPLIT, ROULETTE_BET_STRAIGHT,
+    SWEEP_EPOCH_SLOTS,
+};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    ed25519_instruction::new_ed25519_instruction_with_signature,
+    instruction::InstructionError,
+    signature::Keypair,
+    signer::Signer,
+    sysvar,
+    transaction::{Transaction, TransactionError},
+};
+
+const SOL: u64 = 1_000_000_000;
+const BET: u64 = DEFAULT_MIN_BET_LAMPORTS * 10;
+const INCO_ID: Pubkey = inco_lightning::ID;
+/// One whole unit of a 6-decimal stablecoin
+const TOKEN_BET: u64 = 1_000_000;
+
+struct LpAccounts {
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    mint_authority: Pubkey,
+    depositor_lp: Pubkey,
+    provider: Pubkey,
+    escrow: Pubkey,
+}
+
+/// A whitelisted betting mint and the player's account in it
+struct TokenAccounts {
+    mint: Pubkey,
+    mint_config: Pubkey,
+    token_vault: Pubkey,
+    player_token: Pubkey,
+}
+
+struct Harness {
+    svm: LiteSVM,
+    house: Keypair,
+    player: Keypair,
+    admin: Pubkey,
+    casino_vault: Pubkey,
+    lp_vault: Pubkey,
+    exposure: Pubkey,
+    slot: u64,
+}
+
+fn program_so(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../target/deploy")
+        .join(format!("{name}.so"));
+    assert!(
+        path.exists(),
+        "{} not found, run `anchor build` and `yarn build:mock-inco` first",
+        path.display()
+    );
+    path
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &casino_n_church::ID).0
+}
+
+fn le(value: u128) -> Vec<u8> {
+    value.to_le_bytes().to_vec()
+}
+
+fn custom_error(result: TransactionResult) -> u32 {
+    match result.expect_err("transaction should fail").err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        err => panic!("unexpected error: {err:?}"),
+    }
+}
+
+impl Harness {
+    /// Fresh SVM with both programs, a funded house and player, initialized vaults
+    /// and every game config.
+    fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(casino_n_church::ID, program_so("casino_n_church"))
+            .unwrap();
+        svm.add_program_from_file(INCO_ID, program_so("mock_inco_lightning"))
+            .unwrap();
+
+        let house = Keypair::new();
+        let player = Keypair::new();
+        svm.airdrop(&house.pubkey(), 1_000 * SOL).unwrap();
+        svm.airdrop(&player.pubkey(), 100 * SOL).unwrap();
+
+        let admin = pda(&[b"admin", house.pubkey().as_ref()]);
+        let casino_vault = pda(&[b"casino_vault", admin.as_ref()]);
+        let lp_vault = pda(&[b"lp_vault", admin.as_ref()]);
+        let exposure = pda(&[b"exposure", admin.as_ref()]);
+
+        let mut harness =
+            Self { svm, house, player, admin, casino_vault, lp_vault, exposure, slot: 1 };
+
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::InitializeVault {
+                house: harness.house.pubkey(),
+                casino_vault,
+                lp_vault,
+                admin,
+                exposure,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::InitializeVault { amount: 100 * SOL }.data(),
+        };
+        harness.send_as_house(ix).unwrap();
+
+        for game_id in [GAME_ID_ROULETTE, GAME_ID_COINFLIP, GAME_ID_SLOT, GAME_ID_AVIATOR] {
+            let ix = Instruction {
+                program_id: casino_n_church::ID,
+                accounts: casino_n_church::accounts::InitGameConfig {
+                    authority: harness.house.pubkey(),
+                    admin,
+                    game_config: harness.game_config(game_id),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: casino_n_church::instruction::InitGameConfig { game_id }.data(),
+            };
+            harness.send_as_house(ix).unwrap();
+        }
+
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CreateFaithMint {
+                authority: harness.house.pubkey(),
+                admin,
+                faith_mint: harness.faith_mint(),
+                token_program: anchor_spl::token_2022::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CreateFaithMint {}.data(),
+        };
+        harness.send_as_house(ix).unwrap();
+
+        harness
+    }
+
+    fn game_config(&self, game_id: u8) -> Pubkey {
+        pda(&[b"game_config", self.admin.as_ref(), &[game_id]])
+    }
+
+    fn update_game_config(&mut self, game_id: u8, params: GameConfigParams) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::UpdateGameConfig {
+                authority: self.house.pubkey(),
+                admin: self.admin,
+                game_config: self.game_config(game_id),
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::UpdateGameConfig { params }.data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn game_pda(&self, prefix: &[u8], seed: u128) -> Pubkey {
+        pda(&[prefix, self.player.pubkey().as_ref(), &seed.to_le_bytes()])
+    }
+
+    /// Move to a new slot so e_rand and the blockhash change between plays
+    fn next_slot(&mut self) {
+        self.slot += 1;
+        self.svm.warp_to_slot(self.slot);
+        self.svm.expire_blockhash();
+    }
+
+    fn send(&mut self, ix: Instruction, signer: &Keypair) -> TransactionResult {
+        self.send_all(vec![ix], signer)
+    }
+
+    fn send_all(&mut self, ixs: Vec<Instruction>, signer: &Keypair) -> TransactionResult {
+        let budget = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let tx = Transaction::new_signed_with_payer(
+            &[vec![budget], ixs].concat(),
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx)
+    }
+
+    fn send_as_house(&mut self, ix: Instruction) -> TransactionResult {
+        let house = self.house.insecure_clone();
+        self.send(ix, &house)
+    }
+
+    fn send_as_player(&mut self, ix: Instruction) -> TransactionResult {
+        let player = self.player.insecure_clone();
+        self.send(ix, &player)
+    }
+
+    fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(address).expect("account missing");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    fn is_closed(&self, address: &Pubkey) -> bool {
+        self.svm.get_account(address).is_none_or(|account| account.lamports == 0)
+    }
+
+    fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or(0)
+    }
+
+    fn play_coinflip(
+        &mut self,
+        seed: u128,
+        choice: u128,
+        amount: u64,
+    ) -> (Pubkey, TransactionResult) {
+        let game = self.game_pda(b"game_coinflip", seed);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::PlayCoinflip {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                game_config: self.game_config(GAME_ID_COINFLIP),
+                casino_vault: self.casino_vault,
+                lp_vault: self.lp_vault,
+                exposure: self.exposure,
+                game,
+                profile: self.profile(&self.player.pubkey()),
+                boost: self.active_boost(),
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::PlayCoinflip {
+                seed,
+                encrypted_choice: le(choice),
+                amount,
+            }
+            .data(),
+        };
+        (game, self.send_as_player(ix))
+    }
+
+    fn play_roulette(&mut self, seed: u128, bet_type: u8, choice: u128) -> Pubkey {
+        let game = self.game_pda(b"game_roulette", seed);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::PlayRoulette {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                game_config: self.game_config(GAME_ID_ROULETTE),
+                casino_vault: self.casino_vault,
+                lp_vault: self.lp_vault,
+                exposure: self.exposure,
+                game,
+                profile: self.profile(&self.player.pubkey()),
+                boost: None,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::PlayRoulette {
+                seed,
+                bet_type,
+                encrypted_choice: le(choice),
+                amount: BET,
+            }
+            .data(),
+        };
+        self.send_as_player(ix).unwrap();
+        game
+    }
+
+    fn play_slot(&mut self, seed: u128, amount: u64) -> (Pubkey, TransactionResult) {
+        let game = self.game_pda(b"game_slot", seed);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::PlaySlot {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                game_config: self.game_config(GAME_ID_SLOT),
+                casino_vault: self.casino_vault,
+                lp_vault: self.lp_vault,
+                exposure: self.exposure,
+                game,
+                profile: self.profile(&self.player.pubkey()),
+                boost: None,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::PlaySlot { seed, amount }.data(),
+        };
+        (game, self.send_as_player(ix))
+    }
+
+    fn claim(&mut self, game: Pubkey, handle: u128, plaintext: u128) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::ClaimRewards {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                casino_vault: self.casino_vault,
+                lp_vault: self.lp_vault,
+                exposure: self.exposure,
+                game,
+                legs: None,
+                profile: self.profile(&self.player.pubkey()),
+                instructions: sysvar::instructions::ID,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::ClaimRewards {
+                handle: le(handle),
+                plaintext: le(plaintext),
+            }
+            .data(),
+        };
+        self.send_as_player(ix)
+    }
+
+    fn close_lost_game(
+        &mut self,
+        closer: &Keypair,
+        game: Pubkey,
+        legs: Option<Pubkey>,
+        proof: Option<u128>,
+    ) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CloseLostGame {
+                closer: closer.pubkey(),
+                player: self.player.pubkey(),
+                game,
+                exposure: self.exposure,
+                mint_config: None,
+                legs,
+                instructions: sysvar::instructions::ID,
+                inco_lightning_program: INCO_ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CloseLostGame {
+                proof: proof.map(|payout| PayoutProof {
+                    handle: le(payout),
+                    plaintext: le(payout),
+                }),
+            }
+            .data(),
+        };
+        self.send(ix, closer)
+    }
+
+    fn lp_accounts(&self, depositor: &Pubkey) -> LpAccounts {
+        let lp_mint = pda(&[b"lp_mint", self.admin.as_ref()]);
+        LpAccounts {
+            pool: pda(&[b"liquidity_pool", self.admin.as_ref()]),
+            lp_mint,
+            mint_authority: pda(&[b"lp_mint_authority", self.admin.as_ref()]),
+            depositor_lp: get_associated_token_address(depositor, &lp_mint),
+            provider: pda(&[b"lp_provider", depositor.as_ref(), self.admin.as_ref()]),
+            escrow: pda(&[b"lp_escrow", self.admin.as_ref()]),
+        }
+    }
+
+    fn lp_deposit(&mut self, depositor: &Keypair, amount: u64) -> TransactionResult {
+        let lp = self.lp_accounts(&depositor.pubkey());
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::LpDeposit {
+                depositor: depositor.pubkey(),
+                admin: self.admin,
+                vault: self.lp_vault,
+                casino_vault: self.casino_vault,
+                exposure: self.exposure,
+                pool: lp.pool,
+                lp_mint: lp.lp_mint,
+                mint_authority: lp.mint_authority,
+                depositor_lp: lp.depositor_lp,
+                provider: lp.provider,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::LpDeposit { amount }.data(),
+        };
+        self.send(ix, depositor)
+    }
+
+    fn withdraw_request(&self, id: u64) -> Pubkey {
+        pda(&[b"withdraw_request", self.admin.as_ref(), &id.to_le_bytes()])
+    }
+
+    /// Queue `shares` for redemption; returns the request id
+    fn request_withdraw(
+        &mut self,
+        depositor: &Keypair,
+        shares: u64,
+        allow_partial: bool,
+    ) -> (u64, TransactionResult) {
+        let lp = self.lp_accounts(&depositor.pubkey());
+        let id = self.account::<LiquidityPool>(&lp.pool).queue_tail;
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::RequestWithdraw {
+                depositor: depositor.pubkey(),
+                admin: self.admin,
+                pool: lp.pool,
+                lp_mint: lp.lp_mint,
+                mint_authority: lp.mint_authority,
+                escrow: lp.escrow,
+                depositor_lp: lp.depositor_lp,
+                provider: lp.provider,
+                request: self.withdraw_request(id),
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::RequestWithdraw { shares, allow_partial }.data(),
+        };
+        (id, self.send(ix, depositor))
+    }
+
+    /// Serve request `id` for `owner`, cranked by the house
+    fn execute_withdraw(&mut self, id: u64, owner: &Pubkey) -> TransactionResult {
+        let lp = self.lp_accounts(owner);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::ExecuteWithdraw {
+                cranker: self.house.pubkey(),
+                owner: *owner,
+                admin: self.admin,
+                vault: self.lp_vault,
+                casino_vault: self.casino_vault,
+                exposure: self.exposure,
+                pool: lp.pool,
+                lp_mint: lp.lp_mint,
+                mint_authority: lp.mint_authority,
+                escrow: lp.escrow,
+                request: self.withdraw_request(id),
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::ExecuteWithdraw {}.data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn cancel_withdraw(&mut self, owner: &Keypair, id: u64) -> TransactionResult {
+        let lp = self.lp_accounts(&owner.pubkey());
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CancelWithdraw {
+                owner: owner.pubkey(),
+                admin: self.admin,
+                pool: lp.pool,
+                lp_mint: lp.lp_mint,
+                mint_authority: lp.mint_authority,
+                escrow: lp.escrow,
+                owner_lp: lp.depositor_lp,
+                provider: lp.provider,
+                request: self.withdraw_request(id),
+                token_program: anchor_spl::token::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CancelWithdraw {}.data(),
+        };
+        self.send(ix, owner)
+    }
+
+    /// Move head request `id` to the back of the queue; returns its new id
+    fn requeue_withdraw(&mut self, cranker: &Keypair, id: u64) -> (u64, TransactionResult) {
+        let lp = self.lp_accounts(&cranker.pubkey());
+        let new_id = self.account::<LiquidityPool>(&lp.pool).queue_tail;
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::RequeueWithdraw {
+                cranker: cranker.pubkey(),
+                admin: self.admin,
+                vault: self.lp_vault,
+                casino_vault: self.casino_vault,
+                exposure: self.exposure,
+                pool: lp.pool,
+                lp_mint: lp.lp_mint,
+                request: self.withdraw_request(id),
+                requeued: self.withdraw_request(new_id),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::RequeueWithdraw {}.data(),
+        };
+        (new_id, self.send(ix, cranker))
+    }
+
+    fn skip_withdraw_delay(&mut self) {
+        self.slot += DEFAULT_WITHDRAW_DELAY_SLOTS;
+        self.next_slot();
+    }
+
+    /// Change the admin's default edge and bet limits, keeping its quest fee
+    fn update_config(
+        &mut self,
+        house_edge_bps: u16,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> TransactionResult {
+        let quest_creation_fee = self.account::<Admin>(&self.admin).quest_creation_fee;
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::UpdateConfig {
+                admin: self.admin,
+                authority: self.house.pubkey(),
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::UpdateConfig {
+                house_edge_bps,
+                quest_creation_fee,
+                min_bet,
+                max_bet,
+            }
+            .data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn update_vault_config(
+        &mut self,
+        reserve_target: u64,
+        treasury_share_bps: u16,
+        max_exposure_bps: u16,
+    ) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::UpdateVaultConfig {
+                admin: self.admin,
+                authority: self.house.pubkey(),
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::UpdateVaultConfig {
+                reserve_target,
+                treasury_share_bps,
+                max_exposure_bps,
+            }
+            .data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn update_protocol_fee(&mut self, protocol_fee_bps: u16) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::UpdateProtocolFee {
+                admin: self.admin,
+                authority: self.house.pubkey(),
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::UpdateProtocolFee { protocol_fee_bps }.data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn withdraw_treasury(&mut self, authority: &Keypair, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::WithdrawTreasury {
+                admin: self.admin,
+                authority: authority.pubkey(),
+                casino_vault: self.casino_vault,
+                treasury: self.house.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::WithdrawTreasury { amount }.data(),
+        };
+        self.send(ix, authority)
+    }
+
+    fn rebalance(&mut self, cranker: &Keypair) -> TransactionResult {
+        let lp = self.lp_accounts(&self.player.pubkey());
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::RebalanceVaults {
+                cranker: cranker.pubkey(),
+                admin: self.admin,
+                casino_vault: self.casino_vault,
+                lp_vault: self.lp_vault,
+                pool: lp.pool,
+                lp_mint: lp.lp_mint,
+                exposure: self.exposure,
+                // initialize_vault sets the treasury to the house key
+                treasury: self.house.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::RebalanceVaults {}.data(),
+        };
+        self.send(ix, cranker)
+    }
+
+    /// Write an initialized SPL token account directly into the SVM
+    fn set_token_account(&mut self, address: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_token_program_account(address, data);
+    }
+
+    fn set_token_program_account(&mut self, address: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    /// Whitelist a new 6-decimal mint with limits of 0.1 to 100 `TOKEN_BET`, then give
+    /// its vault `vault_tokens` and the player `player_tokens`
+    fn whitelist_mint(&mut self, vault_tokens: u64, player_tokens: u64) -> TokenAccounts {
+        let mint = Pubkey::new_unique();
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::Some(self.house.pubkey()),
+            supply: vault_tokens + player_tokens,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        self.set_token_program_account(mint, data);
+
+        let tokens = TokenAccounts {
+            mint,
+            mint_config: pda(&[b"mint_config", self.admin.as_ref(), mint.as_ref()]),
+            token_vault: pda(&[b"token_vault", self.admin.as_ref(), mint.as_ref()]),
+            player_token: Pubkey::new_unique(),
+        };
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::InitMintConfig {
+                authority: self.house.pubkey(),
+                admin: self.admin,
+                mint,
+                mint_config: tokens.mint_config,
+                token_vault: tokens.token_vault,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::InitMintConfig {
+                min_bet: TOKEN_BET / 10,
+                max_bet: 100 * TOKEN_BET,
+            }
+            .data(),
+        };
+        self.send_as_house(ix).unwrap();
+
+        self.set_token_account(tokens.token_vault, mint, tokens.mint_config, vault_tokens);
+        self.set_token_account(tokens.player_token, mint, self.player.pubkey(), player_tokens);
+        tokens
+    }
+
+    fn update_mint_config(
+        &mut self,
+        tokens: &TokenAccounts,
+        min_bet: u64,
+        max_bet: u64,
+        enabled: bool,
+    ) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::UpdateMintConfig {
+                authority: self.house.pubkey(),
+                admin: self.admin,
+                mint_config: tokens.mint_config,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::UpdateMintConfig { min_bet, max_bet, enabled }
+                .data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn withdraw_token_vault(
+        &mut self,
+        authority: &Keypair,
+        tokens: &TokenAccounts,
+        amount: u64,
+    ) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::WithdrawTokenVault {
+                authority: authority.pubkey(),
+                admin: self.admin,
+                mint_config: tokens.mint_config,
+                token_vault: tokens.token_vault,
+                destination: tokens.player_token,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::WithdrawTokenVault { amount }.data(),
+        };
+        self.send(ix, authority)
+    }
+
+    fn play_coinflip_token(
+        &mut self,
+        tokens: &TokenAccounts,
+        seed: u128,
+        choice: u128,
+        amount: u64,
+    ) -> (Pubkey, TransactionResult) {
+        let game = self.game_pda(b"game_coinflip", seed);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::PlayCoinflipToken {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                game_config: self.game_config(GAME_ID_COINFLIP),
+                mint_config: tokens.mint_config,
+                token_vault: tokens.token_vault,
+                player_token: tokens.player_token,
+                game,
+                profile: self.profile(&self.player.pubkey()),
+                inco_lightning_program: INCO_ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::PlayCoinflipToken {
+                seed,
+                encrypted_choice: le(choice),
+                amount,
+            }
+            .data(),
+        };
+        (game, self.send_as_player(ix))
+    }
+
+    fn claim_token(
+        &mut self,
+        tokens: &TokenAccounts,
+        game: Pubkey,
+        handle: u128,
+        plaintext: u128,
+    ) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::ClaimTokenRewards {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                mint_config: tokens.mint_config,
+                token_vault: tokens.token_vault,
+                player_token: tokens.player_token,
+                game,
+                profile: self.profile(&self.player.pubkey()),
+                instructions: sysvar::instructions::ID,
+                inco_lightning_program: INCO_ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::ClaimTokenRewards {
+                handle: le(handle),
+                plaintext: le(plaintext),
+            }
+            .data(),
+        };
+        self.send_as_player(ix)
+    }
+
+    /// Token coinflips on successive slots until one pays out
+    fn winning_token_coinflip(&mut self, tokens: &TokenAccounts) -> (Pubkey, GameResult) {
+        for seed in 0..64u128 {
+            self.next_slot();
+            let (game, result) = self.play_coinflip_token(tokens, seed, 0, TOKEN_BET);
+            result.unwrap();
+            let state: GameResult = self.account(&game);
+            if state.payout_handle > 0 {
+                return (game, state);
+            }
+        }
+        panic!("no winning token coinflip in 64 slots");
+    }
+
+    fn token_balance(&self, address: &Pubkey) -> u64 {
+        self.account::<TokenAccount>(address).amount
+    }
+
+    /// LP shares plus the pool's unowned shares, as `LiquidityPool::priced_supply` sees them
+    fn priced_supply(&self) -> u64 {
+        let lp = self.lp_accounts(&self.player.pubkey());
+        let unowned = self.account::<LiquidityPool>(&lp.pool).unowned_shares;
+        self.account::<Mint>(&lp.lp_mint).supply + unowned
+    }
+
+    /// casino_vault lamports above rent and the treasury's accrued share
+    fn casino_float(&self) -> u64 {
+        let rent = self.svm.minimum_balance_for_rent_exemption(0);
+        let admin: Admin = self.account(&self.admin);
+        (self.balance(&self.casino_vault) - rent).saturating_sub(admin.treasury_accrued)
+    }
+
+    /// LP float casino_vault still backs
+    fn lp_float(&self) -> u64 {
+        self.account::<Admin>(&self.admin).lp_float_lent.min(self.casino_float())
+    }
+
+    /// LP vault lamports above the rent-exempt reserve plus the float still backed by
+    /// casino_vault, as `pool_assets` sees them
+    fn pool_assets(&self) -> u64 {
+        let rent = self.svm.minimum_balance_for_rent_exemption(0);
+        self.balance(&self.lp_vault) - rent + self.lp_float()
+    }
+
+    /// Play coinflips on successive slots until one pays out (or doesn't)
+    fn coinflip_with_outcome(&mut self, win: bool) -> (Pubkey, GameResult) {
+        for seed in 0..64u128 {
+            self.next_slot();
+            let (game, result) = self.play_coinflip(seed, 0, BET);
+            result.unwrap();
+            let state: GameResult = self.account(&game);
+            if (state.payout_handle > 0) == win {
+                return (game, state);
+            }
+        }
+        panic!("no coinflip with win = {win} in 64 slots");
+    }
+
+    fn winning_coinflip(&mut self) -> (Pubkey, GameResult) {
+        self.coinflip_with_outcome(true)
+    }
+
+    fn profile(&self, owner: &Pubkey) -> Pubkey {
+        pda(&[b"player_profile", self.admin.as_ref(), owner.as_ref()])
+    }
+
+    fn faith_mint(&self) -> Pubkey {
+        pda(&[b"faith_mint", self.admin.as_ref()])
+    }
+
+    /// The owner's Token-2022 FAITH account
+    fn faith_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            owner,
+            &self.faith_mint(),
+            &anchor_spl::token_2022::ID,
+        )
+    }
+
+    fn faith_balance(&self, owner: &Pubkey) -> u64 {
+        let account = self.faith_account(owner);
+        self.account::<anchor_spl::token_interface::TokenAccount>(&account).amount
+    }
+
+    /// Writing quest by the player, judged by the house
+    fn create_writing_quest(
+        &mut self,
+        quest_id: u64,
+        faith_reward: u64,
+        max_approvals: u32,
+    ) -> (Pubkey, TransactionResult) {
+        let creator = self.player.pubkey();
+        let id = quest_id.to_le_bytes();
+        let quest = pda(&[b"writing_quest", self.admin.as_ref(), creator.as_ref(), &id]);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CreateWritingQuest {
+                creator,
+                admin: self.admin,
+                treasury: self.house.pubkey(),
+                promo_vault: self.promo_vault(),
+                quest,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CreateWritingQuest {
+                quest_id,
+                params: WritingQuestParams {
+                    curator: self.house.pubkey(),
+                    prompt_hash: [7; 32],
+                    prompt_uri: "ipfs://prompt".to_string(),
+                    faith_reward,
+                    deadline_slot: self.slot + 10,
+                    max_approvals,
+                },
+            }
+            .data(),
+        };
+        (quest, self.send_as_player(ix))
+    }
+
+    fn submit_sermon(&mut self, author: &Keypair, quest: Pubkey) -> (Pubkey, TransactionResult) {
+        let submission = pda(&[b"sermon", quest.as_ref(), author.pubkey().as_ref()]);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::SubmitSermon {
+                author: author.pubkey(),
+                admin: self.admin,
+                quest,
+                submission,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::SubmitSermon {
+                content_hash: [9; 32],
+                uri: "ipfs://sermon".to_string(),
+            }
+            .data(),
+        };
+        (submission, self.send(ix, author))
+    }
+
+    fn approve_submission(
+        &mut self,
+        curator: &Keypair,
+        quest: Pubkey,
+        author: &Pubkey,
+    ) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::ApproveSubmission {
+                curator: curator.pubkey(),
+                admin: self.admin,
+                quest,
+                submission: pda(&[b"sermon", quest.as_ref(), author.as_ref()]),
+                profile: self.profile(author),
+                faith_mint: self.faith_mint(),
+                author: *author,
+                author_faith: self.faith_account(author),
+                token_program: anchor_spl::token_2022::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::ApproveSubmission {}.data(),
+        };
+        self.send(ix, curator)
+    }
+
+    fn quest_verifier(&self, verifier: &Pubkey) -> Pubkey {
+        pda(&[b"quest_verifier", self.admin.as_ref(), verifier.as_ref()])
+    }
+
+    fn set_quest_verifier(&mut self, verifier: &Pubkey, enabled: bool) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::SetQuestVerifier {
+                authority: self.house.pubkey(),
+                admin: self.admin,
+                quest_verifier: self.quest_verifier(verifier),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::SetQuestVerifier { verifier: *verifier, enabled }
+                .data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    /// Blink quest with a 50 FAITH reward and a 0.1 SOL bounty for each completion
+    fn create_blink_quest(
+        &mut self,
+        creator: &Keypair,
+        verifier: &Pubkey,
+        max_completions: u32,
+    ) -> (Pubkey, TransactionResult) {
+        let creator_key = creator.pubkey();
+        let id = 0u64.to_le_bytes();
+        let quest = pda(&[b"blink_quest", self.admin.as_ref(), creator_key.as_ref(), &id]);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CreateBlinkQuest {
+                creator: creator_key,
+                admin: self.admin,
+                treasury: self.house.pubkey(),
+                promo_vault: self.promo_vault(),
+                verifier_approval: self.quest_verifier(verifier),
+                quest,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CreateBlinkQuest {
+                quest_id: 0,
+                params: BlinkQuestParams {
+                    verifier: *verifier,
+                    uri: "https://dial.to/?action=solana-action:quest".to_string(),
+                    faith_reward: 50,
+                    bounty: SOL / 10,
+                    deadline_slot: self.slot + 10,
+                    max_completions,
+                },
+            }
+            .data(),
+        };
+        (quest, self.send(ix, creator))
+    }
+
+    /// Complete a blink quest, preceded by `signer`'s Ed25519 attestation if given
+    fn complete_blink_quest(
+        &mut self,
+        completer: &Keypair,
+        quest: Pubkey,
+        signer: Option<&Keypair>,
+    ) -> TransactionResult {
+        let verifier = self.account::<BlinkQuest>(&quest).verifier;
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CompleteBlinkQuest {
+                completer: completer.pubkey(),
+                admin: self.admin,
+                quest,
+                verifier_approval: self.quest_verifier(&verifier),
+                completion: pda(&[
+                    b"blink_completion",
+                    quest.as_ref(),
+                    completer.pubkey().as_ref(),
+                ]),
+                profile: self.profile(&completer.pubkey()),
+                faith_mint: self.faith_mint(),
+                completer_faith: self.faith_account(&completer.pubkey()),
+                instructions: sysvar::instructions::ID,
+                token_program: anchor_spl::token_2022::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CompleteBlinkQuest {}.data(),
+        };
+        let mut ixs = vec![];
+        if let Some(signer) = signer {
+            let message = BlinkQuest::attestation_message(&quest, &completer.pubkey());
+            let signature = signer.sign_message(&message);
+            ixs.push(new_ed25519_instruction_with_signature(
+                &message,
+                signature.as_ref().try_into().unwrap(),
+                &signer.pubkey().to_bytes(),
+            ));
+        }
+        ixs.push(ix);
+        self.send_all(ixs, completer)
+    }
+
+    fn boost(&self, owner: &Pubkey) -> Pubkey {
+        pda(&[b"boost", self.admin.as_ref(), owner.as_ref()])
+    }
+
+    /// The player's unspent voucher, which their next coinflip consumes
+    fn active_boost(&self) -> Option<Pubkey> {
+        let boost = self.boost(&self.player.pubkey());
+        (!self.is_closed(&boost)).then_some(boost)
+    }
+
+    fn promo_vault(&self) -> Pubkey {
+        pda(&[b"promo_vault", self.admin.as_ref()])
+    }
+
+    fn fund_promo_budget(&mut self, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::FundPromoBudget {
+                authority: self.house.pubkey(),
+                admin: self.admin,
+                promo_vault: self.promo_vault(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::FundPromoBudget { amount }.data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    /// Redeem a voucher sized for `BET`, or exactly its credit for a free bet
+    fn redeem_faith(&mut self, kind: BoostKind, value: u64) -> TransactionResult {
+        let max_stake = if kind == BoostKind::FreeBet { value } else { BET };
+        self.redeem_faith_up_to(kind, value, max_stake)
+    }
+
+    fn redeem_faith_up_to(
+        &mut self,
+        kind: BoostKind,
+        value: u64,
+        max_stake: u64,
+    ) -> TransactionResult {
+        let player = self.player.pubkey();
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::RedeemFaith {
+                player,
+                admin: self.admin,
+                profile: self.profile(&player),
+                boost: self.boost(&player),
+                promo_vault: self.promo_vault(),
+                faith_mint: self.faith_mint(),
+                player_faith: self.faith_account(&player),
+                token_program: anchor_spl::token_2022::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::RedeemFaith { kind, value, max_stake }.data(),
+        };
+        self.send_as_player(ix)
+    }
+
+    fn cancel_boost(&mut self, owner: &Keypair) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CancelBoost {
+                owner: owner.pubkey(),
+                admin: self.admin,
+                boost: self.boost(&owner.pubkey()),
+                promo_vault: self.promo_vault(),
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CancelBoost {}.data(),
+        };
+        self.send(ix, owner)
+    }
+
+    fn encrypted_balance(&self, owner: &Pubkey) -> Pubkey {
+        pda(&[b"encrypted_balance", self.admin.as_ref(), owner.as_ref()])
+    }
+
+    fn house_balance(&self) -> Pubkey {
+        pda(&[b"house_balance", self.admin.as_ref()])
+    }
+
+    fn balance_vault(&self) -> Pubkey {
+        pda(&[b"balance_vault", self.admin.as_ref()])
+    }
+
+    /// Plaintext of an encrypted balance (the mock's handles are their own plaintext)
+    fn balance_handle(&self, balance: &Pubkey) -> u128 {
+        self.account::<EncryptedBalance>(balance).handle
+    }
+
+    fn deposit_balance(&mut self, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::DepositBalance {
+                depositor: self.player.pubkey(),
+                admin: self.admin,
+                balance_vault: self.balance_vault(),
+                balance: self.encrypted_balance(&self.player.pubkey()),
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::DepositBalance { amount }.data(),
+        };
+        self.send_as_player(ix)
+    }
+
+    fn fund_house_balance(&mut self, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::FundHouseBalance {
+                authority: self.house.pubkey(),
+                admin: self.admin,
+                balance_vault: self.balance_vault(),
+                house_balance: self.house_balance(),
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::FundHouseBalance { amount }.data(),
+        };
+        self.send_as_house(ix)
+    }
+
+    fn play_confidential(
+        &mut self,
+        seed: u128,
+        game_type: GameType,
+        choice: u128,
+        amount: u128,
+    ) -> (Pubkey, TransactionResult) {
+        let game = self.game_pda(b"game_confidential", seed);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::PlayConfidential {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                game_config: self.game_config(game_type.id()),
+                balance: self.encrypted_balance(&self.player.pubkey()),
+                house_balance: self.house_balance(),
+                game,
+                profile: self.profile(&self.player.pubkey()),
+                boost: None,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::PlayConfidential {
+                seed,
+                game_type,
+                encrypted_choice: le(choice),
+                encrypted_amount: le(amount),
+            }
+            .data(),
+        };
+        (game, self.send_as_player(ix))
+    }
+
+    fn claim_to_balance(&mut self, game: Pubkey) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::ClaimToBalance {
+                player: self.player.pubkey(),
+                admin: self.admin,
+                balance: self.encrypted_balance(&self.player.pubkey()),
+                game,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::ClaimToBalance {}.data(),
+        };
+        self.send_as_player(ix)
+    }
+
+    fn request_balance_withdraw(&mut self, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::RequestBalanceWithdraw {
+                owner: self.player.pubkey(),
+                admin: self.admin,
+                balance: self.encrypted_balance(&self.player.pubkey()),
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::RequestBalanceWithdraw { amount }.data(),
+        };
+        self.send_as_player(ix)
+    }
+
+    fn complete_balance_withdraw(&mut self, handle: u128, plaintext: u128) -> TransactionResult {
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::CompleteBalanceWithdraw {
+                owner: self.player.pubkey(),
+                admin: self.admin,
+                balance_vault: self.balance_vault(),
+                balance: self.encrypted_balance(&self.player.pubkey()),
+                instructions: sysvar::instructions::ID,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::CompleteBalanceWithdraw {
+                handle: le(handle),
+                plaintext: le(plaintext),
+            }
+            .data(),
+        };
+        self.send_as_player(ix)
+    }
+}
+
+#[test]
+fn game_configs_start_from_admin_defaults() {
+    let mut h = Harness::new();
+    let config: GameConfig = h.account(&h.game_config(GAME_ID_SLOT));
+    assert_eq!(config.house_edge_bps, DEFAULT_HOUSE_EDGE_BPS);
+    assert_eq!(config.min_bet, DEFAULT_MIN_BET_LAMPORTS);
+    assert_eq!(config.jackpot_multiplier, DEFAULT_SLOT_JACKPOT_MULTIPLIER);
+    assert!(!config.paused);
+
+    // Changing the defaults leaves games that are already configured alone
+    h.update_config(300, 2 * DEFAULT_MIN_BET_LAMPORTS, SOL).unwrap();
+    assert_eq!(h.account::<Admin>(&h.admin).house_edge_bps, 300);
+    let config: GameConfig = h.account(&h.game_config(GAME_ID_SLOT));
+    assert_eq!(config.house_edge_bps, DEFAULT_HOUSE_EDGE_BPS);
+    assert_eq!(config.min_bet, DEFAULT_MIN_BET_LAMPORTS);
+}
+
+#[test]
+fn slot_multipliers_must_leave_the_house_an_edge() {
+    let mut h = Harness::new();
+    let config: GameConfig = h.account(&h.game_config(GAME_ID_SLOT));
+    let params = |jackpot_multiplier, small_win_multiplier| GameConfigParams {
+        min_bet: config.min_bet,
+        max_bet: config.max_bet,
+        house_edge_bps: config.house_edge_bps,
+        straight_multiplier: config.straight_multiplier,
+        jackpot_multiplier,
+        small_win_multiplier,
+        max_multiplier_bps: config.max_multiplier_bps,
+        paused: config.paused,
+    };
+    // 50x on a 1% jackpot and 5x on the 18% pair hand back 140% of every stake
+    let result = h.update_game_config(GAME_ID_SLOT, params(50, 5));
+    assert_eq!(custom_error(result), u32::from(CasinoError::InvalidConfig));
+    let result = h.update_game_config(GAME_ID_SLOT, params(46, 3));
+    assert_eq!(custom_error(result), u32::from(CasinoError::InvalidConfig));
+    h.update_game_config(GAME_ID_SLOT, params(45, 3)).unwrap();
+}
+
+#[test]
+fn coinflip_pays_only_on_matching_flip() {
+    let mut h = Harness::new();
+    let win = calculate_win_payout(BET, 2, DEFAULT_HOUSE_EDGE_BPS).unwrap();
+    for seed in 0..8u128 {
+        h.next_slot();
+        let choice = seed % 2;
+        let (game, result) = h.play_coinflip(seed, choice, BET);
+        result.unwrap();
+        let state: GameResult = h.account(&game);
+        let flip = state.random_handles[0];
+        assert!(flip < 2);
+        assert_eq!(state.choice_handle, choice);
+        assert_eq!(state.payout_handle, if flip == choice { win as u128 } else { 0 });
+    }
+}
+
+#[test]
+fn roulette_payouts_follow_the_table() {
+    let mut h = Harness::new();
+    let bets = [
+        (ROULETTE_BET_STRAIGHT, 17),
+        (ROULETTE_BET_COLOR, 0),
+        (ROULETTE_BET_DOZEN, 2),
+        (ROULETTE_BET_SPLIT, 17 * 37 + 20),
+    ];
+    for (seed, (bet_type, choice)) in bets.into_iter().enumerate() {
+        h.next_slot();
+        let game = h.play_roulette(seed as u128, bet_type, choice as u128);
+        let state: GameResult = h.account(&game);
+        let spin = state.random_handles[0] as u64;
+        assert!(spin <= 36);
+        let multiplier = roulette_multiplier(bet_type, 36).unwrap();
+        let win = calculate_win_payout(BET, multiplier, DEFAULT_HOUSE_EDGE_BPS).unwrap();
+        let expected = if roulette_bet_wins(bet_type, choice, spin) { win } else { 0 };
+        assert_eq!(state.payout_handle, expected as u128, "bet_type {bet_type} spin {spin}");
+    }
+}
+
+#[test]
+fn roulette_multi_sums_leg_payouts() {
+    let mut h = Harness::new();
+    let seed = 7u128;
+    let game = h.game_pda(b"game_roulette", seed);
+    let legs = pda(&[b"roulette_legs", game.as_ref()]);
+    let inputs = vec![
+        RouletteLegInput { bet_type: ROULETTE_BET_COLOR, encrypted_choice: le(1), amount: BET },
+        RouletteLegInput { bet_type: ROULETTE_BET_DOZEN, encrypted_choice: le(0), amount: BET },
+        RouletteLegInput { bet_type: ROULETTE_BET_STRAIGHT, encrypted_choice: le(0), amount: BET },
+    ];
+    let ix = Instruction {
+        program_id: casino_n_church::ID,
+        accounts: casino_n_church::accounts::PlayRouletteMulti {
+            player: h.player.pubkey(),
+            admin: h.admin,
+            game_config: h.game_config(GAME_ID_ROULETTE),
+            casino_vault: h.casino_vault,
+            lp_vault: h.lp_vault,
+            exposure: h.exposure,
+            game,
+            profile: h.profile(&h.player.pubkey()),
+            boost: None,
+            legs,
+            inco_lightning_program: INCO_ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: casino_n_church::instruction::PlayRouletteMulti { seed, legs: inputs }.data(),
+    };
+    h.send_as_player(ix).unwrap();
+
+    let state: GameResult = h.account(&game);
+    let recorded: RouletteSpinLegs = h.account(&legs);
+    let spin = state.random_handles[0] as u64;
+    assert_eq!(state.bet_amount, 3 * BET);
+    assert_eq!(recorded.legs.len(), 3);
+
+    let mut total = 0u128;
+    for leg in &recorded.legs {
+        let multiplier = roulette_multiplier(leg.bet_type, 36).unwrap();
+        let win = calculate_win_payout(leg.amount, multiplier, DEFAULT_HOUSE_EDGE_BPS).unwrap();
+        let wins = roulette_bet_wins(leg.bet_type, leg.choice_handle as u64, spin);
+        assert_eq!(leg.payout_handle, if wins { win as u128 } else { 0 });
+        total += leg.payout_handle;
+    }
+    assert_eq!(state.payout_handle, total);
+}
+
+#[test]
+fn slot_pays_jackpot_small_win_or_nothing() {
+    let mut h = Harness::new();
+    let jackpot = calculate_win_payout(BET, DEFAULT_SLOT_JACKPOT_MULTIPLIER, DEFAULT_HOUSE_EDGE_BPS)
+        .unwrap();
+    let small = calculate_win_payout(BET, DEFAULT_SLOT_SMALL_WIN_MULTIPLIER, DEFAULT_HOUSE_EDGE_BPS)
+        .unwrap();
+    for seed in 0..8u128 {
+        h.next_slot();
+        let (game, result) = h.play_slot(seed, BET);
+        result.unwrap();
+
+        let state: GameResult = h.account(&game);
+        let [r1, r2, r3, _] = state.random_handles;
+        let expected = match (r1 == r2, r2 == r3) {
+            (true, true) => jackpot,
+            (true, false) | (false, true) => small,
+            (false, false) => 0,
+        };
+        assert_eq!(state.payout_handle, expected as u128, "reels {r1} {r2} {r3}");
+    }
+}
+
+#[test]
+fn aviator_pays_target_multiple_below_crash() {
+    let mut h = Harness::new();
+    let cap = 50_000u32;
+    for (seed, target) in [(0u128, 10_100u128), (1, 15_000), (2, 50_000), (3, 60_000)] {
+        h.next_slot();
+        let game = h.game_pda(b"game_aviator", seed);
+        let ix = Instruction {
+            program_id: casino_n_church::ID,
+            accounts: casino_n_church::accounts::PlayAviator {
+                player: h.player.pubkey(),
+                admin: h.admin,
+                game_config: h.game_config(GAME_ID_AVIATOR),
+                casino_vault: h.casino_vault,
+                lp_vault: h.lp_vault,
+                exposure: h.exposure,
+                game,
+                profile: h.profile(&h.player.pubkey()),
+                boost: None,
+                inco_lightning_program: INCO_ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: casino_n_church::instruction::PlayAviator {
+                seed,
+                encrypted_target_multiplier: le(target),
+                target_cap_bps: cap,
+                amount: BET,
+            }
+            .data(),
+        };
+        h.send_as_player(ix).unwrap();
+
+        let state: GameResult = h.account(&game);
+        let crash = state.random_handles[0];
+        let ceiling = h.account::<GameConfig>(&h.game_config(GAME_ID_AVIATOR)).max_multiplier_bps;
+        assert!((BPS as u128..=ceiling as u128).contains(&crash), "crash {crash}");
+        let pays = crash >= target && target <= cap as u128;
+        let expected = if pays {
+            calculate_aviator_payout(BET, target as u64, DEFAULT_HOUSE_EDGE_BPS).unwrap()
+        } else {
+            0
+        };
+        assert_eq!(state.payout_handle, expected as u128, "target {target} crash {crash}");
+    }
+}
+
+#[test]
+fn claim_pays_winner_once() {
+    let mut h = Harness::new();
+    let (game, state) = h.winning_coinflip();
+    let payout = state.payout_handle;
+
+    let vault_before = h.balance(&h.casino_vault);
+    let rent = h.balance(&game);
+    let player_before = h.balance(&h.player.pubkey());
+    h.claim(game, payout, payout).unwrap();
+    assert_eq!(h.balance(&h.casino_vault), vault_before - payout as u64);
+
+    // Game account is closed and its rent refunded (less the 5000 lamport fee)
+    assert!(h.is_closed(&game));
+    assert_eq!(h.balance(&h.player.pubkey()), player_before + payout as u64 + rent - 5_000);
+
+    h.next_slot();
+    assert!(h.claim(game, payout, payout).is_err());
+}
+
+#[test]
+fn payouts_the_lp_vault_funds_still_count_against_house_profit() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    let (game, state) = h.winning_coinflip();
+    let payout = state.payout_handle as u64;
+    let wagered = h.account::<Admin>(&h.admin).total_wagered;
+
+    // Leave the casino float one lamport short of the payout
+    let rent = h.svm.minimum_balance_for_rent_exemption(0);
+    let mut vault = h.svm.get_account(&h.casino_vault).unwrap();
+    vault.lamports = rent + payout - 1;
+    h.svm.set_account(h.casino_vault, vault).unwrap();
+    let lp_before = h.balance(&h.lp_vault);
+
+    h.claim(game, state.payout_handle, state.payout_handle).unwrap();
+    assert_eq!(h.balance(&h.lp_vault), lp_before - 1);
+    let admin: Admin = h.account(&h.admin);
+    assert_eq!(admin.total_paid_out, payout);
+    assert_eq!(admin.unswept_profit(), wagered.saturating_sub(payout));
+}
+
+#[test]
+fn claim_rejects_inflated_plaintext() {
+    let mut h = Harness::new();
+    let (game, state) = h.winning_coinflip();
+    assert!(h.claim(game, state.payout_handle, state.payout_handle * 2).is_err());
+    assert!(!h.account::<GameResult>(&game).claimed);
+}
+
+#[test]
+fn claim_rejects_another_games_handle() {
+    let mut h = Harness::new();
+    let (game, state) = h.winning_coinflip();
+    let foreign = state.payout_handle + 1;
+    let code = custom_error(h.claim(game, foreign, foreign));
+    assert_eq!(code, u32::from(CasinoError::PayoutHandleMismatch));
+}
+
+#[test]
+fn lost_game_can_be_closed_by_anyone_with_a_zero_proof() {
+    let mut h = Harness::new();
+    let (game, _) = h.coinflip_with_outcome(false);
+    let rent = h.balance(&game);
+    let player_before = h.balance(&h.player.pubkey());
+
+    let stranger = Keypair::new();
+    h.svm.airdrop(&stranger.pubkey(), SOL).unwrap();
+    h.close_lost_game(&stranger, game, None, Some(0)).unwrap();
+
+    assert!(h.is_closed(&game));
+    assert_eq!(h.balance(&h.player.pubkey()), player_before + rent);
+}
+
+#[test]
+fn only_the_player_can_forfeit() {
+    let mut h = Harness::new();
+    let (game, state) = h.winning_coinflip();
+
+    // A winning game cannot be closed as lost...
+    let code = custom_error(h.close_lost_game(
+        &h.house.insecure_clone(),
+        game,
+        None,
+        Some(state.payout_handle),
+    ));
+    assert_eq!(code, u32::from(CasinoError::GameNotLost));
+
+    // ...or forfeited by someone else
+    let code = custom_error(h.close_lost_game(&h.house.insecure_clone(), game, None, None));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+
+    let player = h.player.insecure_clone();
+    h.close_lost_game(&player, game, None, None).unwrap();
+    assert!(h.is_closed(&game));
+}
+
+#[test]
+fn abandoned_games_expire_and_release_their_reservation() {
+    let mut h = Harness::new();
+    let (game, state) = h.winning_coinflip();
+    let exposure: Exposure = h.account(&h.exposure);
+    assert_eq!(exposure.outstanding, state.max_payout);
+
+    let stranger = Keypair::new();
+    h.svm.airdrop(&stranger.pubkey(), SOL).unwrap();
+    h.slot = state.slot + GAME_EXPIRY_SLOTS - 2;
+    h.next_slot();
+    let code = custom_error(h.close_lost_game(&stranger, game, None, None));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+
+    h.next_slot();
+    let rent = h.balance(&game);
+    let player_before = h.balance(&h.player.pubkey());
+    h.close_lost_game(&stranger, game, None, None).unwrap();
+    assert!(h.is_closed(&game));
+    assert_eq!(h.balance(&h.player.pubkey()), player_before + rent);
+
+    let exposure: Exposure = h.account(&h.exposure);
+    assert_eq!((exposure.outstanding, exposure.open_games), (0, 0));
+}
+
+#[test]
+fn multi_leg_games_must_close_their_legs() {
+    let mut h = Harness::new();
+    let seed = 3u128;
+    let game = h.game_pda(b"game_roulette", seed);
+    let legs = pda(&[b"roulette_legs", game.as_ref()]);
+    let ix = Instruction {
+        program_id: casino_n_church::ID,
+        accounts: casino_n_church::accounts::PlayRouletteMulti {
+            player: h.player.pubkey(),
+            admin: h.admin,
+            game_config: h.game_config(GAME_ID_ROULETTE),
+            casino_vault: h.casino_vault,
+            lp_vault: h.lp_vault,
+            exposure: h.exposure,
+            game,
+            profile: h.profile(&h.player.pubkey()),
+            boost: None,
+            legs,
+            inco_lightning_program: INCO_ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: casino_n_church::instruction::PlayRouletteMulti {
+            seed,
+            legs: vec![RouletteLegInput {
+                bet_type: ROULETTE_BET_COLOR,
+                encrypted_choice: le(0),
+                amount: BET,
+            }],
+        }
+        .data(),
+    };
+    h.send_as_player(ix).unwrap();
+
+    let player = h.player.insecure_clone();
+    let code = custom_error(h.close_lost_game(&player, game, None, None));
+    assert_eq!(code, u32::from(CasinoError::MissingRouletteLegs));
+
+    h.next_slot();
+    h.close_lost_game(&player, game, Some(legs), None).unwrap();
+    assert!(h.is_closed(&legs));
+}
+
+#[test]
+fn rebalance_splits_profit_between_lps_and_treasury_once_per_epoch() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.update_vault_config(50 * SOL, 2_000, DEFAULT_MAX_EXPOSURE_BPS).unwrap();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+
+    // Unclaimed games leave their stakes in the casino vault
+    for seed in 0..4u128 {
+        h.next_slot();
+        h.play_coinflip(seed, 0, BET).1.unwrap();
+    }
+    let admin: Admin = h.account(&h.admin);
+    assert_eq!(admin.total_wagered, 4 * BET);
+    assert_eq!(admin.unswept_profit(), 4 * BET);
+
+    // Surplus above the reserve, but the epoch has not elapsed yet
+    let lp_before = h.balance(&h.lp_vault);
+    h.rebalance(&player).unwrap();
+    assert_eq!(h.balance(&h.lp_vault), lp_before);
+
+    h.slot += SWEEP_EPOCH_SLOTS;
+    h.next_slot();
+    let treasury_before = h.balance(&h.house.pubkey());
+    h.rebalance(&player).unwrap();
+    let to_treasury = 4 * BET / 5;
+    assert_eq!(h.balance(&h.house.pubkey()), treasury_before + to_treasury);
+    assert_eq!(h.balance(&h.lp_vault), lp_before + 4 * BET - to_treasury);
+
+    let admin: Admin = h.account(&h.admin);
+    assert_eq!(admin.total_profit_swept, 4 * BET);
+    assert_eq!(admin.unswept_profit(), 0);
+}
+
+#[test]
+fn rebalance_refills_the_reserve_from_lps_and_repays_it_first() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+
+    let rent = h.svm.minimum_balance_for_rent_exemption(0);
+    let casino_free = h.balance(&h.casino_vault) - rent;
+    h.update_vault_config(casino_free + 30 * SOL, 0, DEFAULT_MAX_EXPOSURE_BPS).unwrap();
+    let lp_before = h.balance(&h.lp_vault);
+    let nav_before = (h.pool_assets(), h.priced_supply());
+    h.rebalance(&player).unwrap();
+    assert_eq!(h.balance(&h.casino_vault) - rent, casino_free + 30 * SOL);
+    assert_eq!(h.balance(&h.lp_vault), lp_before - 30 * SOL);
+    assert_eq!(h.account::<Admin>(&h.admin).lp_float_lent, 30 * SOL);
+
+    // The lent float still backs LP shares, so the next depositor pays the same NAV
+    assert_eq!((h.pool_assets(), h.priced_supply()), nav_before);
+    let lp = h.lp_accounts(&player.pubkey());
+    let shares_before = h.token_balance(&lp.depositor_lp);
+    h.lp_deposit(&player, SOL).unwrap();
+    let (assets, supply) = nav_before;
+    assert_eq!(
+        h.token_balance(&lp.depositor_lp) - shares_before,
+        (SOL as u128 * supply as u128 / assets as u128) as u64
+    );
+
+    // Lowering the target returns the float to LPs before anything else
+    h.next_slot();
+    h.update_vault_config(casino_free - 10 * SOL, 0, DEFAULT_MAX_EXPOSURE_BPS).unwrap();
+    h.rebalance(&player).unwrap();
+    assert_eq!(h.balance(&h.lp_vault), lp_before + SOL);
+    assert_eq!(h.account::<Admin>(&h.admin).lp_float_lent, 0);
+}
+
+#[test]
+fn reserve_targets_must_cover_a_max_bet_and_stay_bounded() {
+    let mut h = Harness::new();
+    let max_bet = h.account::<Admin>(&h.admin).max_bet;
+    for target in [0, max_bet - 1, MAX_RESERVE_TARGET_LAMPORTS + 1] {
+        let result = h.update_vault_config(target, 0, DEFAULT_MAX_EXPOSURE_BPS);
+        assert_eq!(custom_error(result), u32::from(CasinoError::InvalidConfig));
+    }
+    h.update_vault_config(max_bet, 0, DEFAULT_MAX_EXPOSURE_BPS).unwrap();
+
+    // Nor may the default max bet be raised past the target afterwards
+    let edge = DEFAULT_HOUSE_EDGE_BPS;
+    let result = h.update_config(edge, DEFAULT_MIN_BET_LAMPORTS, max_bet + 1);
+    assert_eq!(custom_error(result), u32::from(CasinoError::InvalidConfig));
+    h.update_config(edge, DEFAULT_MIN_BET_LAMPORTS, max_bet).unwrap();
+    h.update_vault_config(MAX_RESERVE_TARGET_LAMPORTS, 0, DEFAULT_MAX_EXPOSURE_BPS).unwrap();
+}
+
+#[test]
+fn protocol_fees_accrue_per_wager_and_only_the_authority_withdraws() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.update_protocol_fee(2_000).unwrap();
+    for seed in 0..3u128 {
+        h.next_slot();
+        h.play_coinflip(seed, 0, BET).1.unwrap();
+    }
+    let fee = 3 * protocol_fee(BET, DEFAULT_HOUSE_EDGE_BPS, 2_000).unwrap();
+    let admin: Admin = h.account(&h.admin);
+    assert_eq!(admin.treasury_accrued, fee);
+    assert_eq!(admin.total_protocol_fees, fee);
+    // Fees are not house profit, so LPs never see them
+    assert_eq!(admin.unswept_profit(), 3 * BET - fee);
+
+    let code = custom_error(h.withdraw_treasury(&player, fee));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+    let house = h.house.insecure_clone();
+    let code = custom_error(h.withdraw_treasury(&house, fee + 1));
+    assert_eq!(code, u32::from(CasinoError::InsufficientVaultFunds));
+
+    // The house key is both authority and treasury, so it also pays the fee
+    let treasury_before = h.balance(&h.house.pubkey());
+    h.withdraw_treasury(&house, fee).unwrap();
+    assert_eq!(h.balance(&h.house.pubkey()), treasury_before + fee - 5_000);
+    assert_eq!(h.account::<Admin>(&h.admin).treasury_accrued, 0);
+}
+
+#[test]
+fn open_games_hold_their_worst_case_payout_until_resolved() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    let (won, state) = h.winning_coinflip();
+    let (lost, _) = h.coinflip_with_outcome(false);
+
+    let worst_case = calculate_win_payout(BET, 2, DEFAULT_HOUSE_EDGE_BPS).unwrap();
+    assert_eq!(state.max_payout, worst_case);
+    let exposure: Exposure = h.account(&h.exposure);
+    assert_eq!(exposure.open_games, 2);
+    assert_eq!(exposure.outstanding, 2 * worst_case);
+
+    h.claim(won, state.payout_handle, state.payout_handle).unwrap();
+    h.close_lost_game(&player, lost, None, Some(0)).unwrap();
+    let exposure: Exposure = h.account(&h.exposure);
+    assert_eq!(exposure.open_games, 0);
+    assert_eq!(exposure.outstanding, 0);
+}
+
+#[test]
+fn bets_beyond_the_exposure_limit_are_rejected() {
+    let mut h = Harness::new();
+    // 0.1% of the ~200 SOL bankroll covers one coinflip at BET but not at twice that
+    h.update_vault_config(DEFAULT_RESERVE_TARGET_LAMPORTS, 0, 10).unwrap();
+    h.play_coinflip(0, 0, BET).1.unwrap();
+    let (_, result) = h.play_coinflip(1, 0, 2 * BET);
+    assert_eq!(custom_error(result), u32::from(CasinoError::ExposureLimitExceeded));
+}
+
+#[test]
+fn lp_exits_cannot_uncover_unclaimed_games() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    let shares = h.token_balance(&h.lp_accounts(&player.pubkey()).depositor_lp);
+
+    // A 3.5 SOL spin's jackpot ties up nearly the whole ~208 SOL bankroll
+    h.update_vault_config(DEFAULT_RESERVE_TARGET_LAMPORTS, 0, 10_000).unwrap();
+    let (game, result) = h.play_slot(0, 35 * SOL / 10);
+    result.unwrap();
+
+    // Only the ~1.6 SOL the jackpot leaves uncovered can leave; the rest stays queued
+    let (id, result) = h.request_withdraw(&player, shares, true);
+    result.unwrap();
+    h.skip_withdraw_delay();
+    let lp_before = h.balance(&h.lp_vault);
+    h.execute_withdraw(id, &player.pubkey()).unwrap();
+    let paid = lp_before - h.balance(&h.lp_vault);
+    assert!(paid > SOL && paid < 2 * SOL, "paid {paid}");
+    let remaining = h.account::<WithdrawRequest>(&h.withdraw_request(id)).shares;
+    assert!(remaining > 0 && remaining < shares);
+
+    h.next_slot();
+    let code = custom_error(h.execute_withdraw(id, &player.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::LiabilityNotCovered));
+
+    // Once the game is resolved its liability is released
+    h.close_lost_game(&player, game, None, None).unwrap();
+    h.execute_withdraw(id, &player.pubkey()).unwrap();
+    assert!(h.is_closed(&h.withdraw_request(id)));
+    assert_eq!(h.token_balance(&h.lp_accounts(&player.pubkey()).escrow), 0);
+}
+
+#[test]
+fn all_or_nothing_withdrawals_wait_for_full_liquidity() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    let lp = h.lp_accounts(&player.pubkey());
+    let shares = h.token_balance(&lp.depositor_lp);
+
+    h.update_vault_config(DEFAULT_RESERVE_TARGET_LAMPORTS, 0, 10_000).unwrap();
+    let (game, result) = h.play_slot(0, 35 * SOL / 10);
+    result.unwrap();
+
+    let (id, result) = h.request_withdraw(&player, shares, false);
+    result.unwrap();
+    h.skip_withdraw_delay();
+    let code = custom_error(h.execute_withdraw(id, &player.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::WithdrawNotFillable));
+    assert_eq!(h.token_balance(&lp.escrow), shares);
+
+    h.close_lost_game(&player, game, None, None).unwrap();
+    h.execute_withdraw(id, &player.pubkey()).unwrap();
+
+    // Principal left the pool with the shares
+    let pool: LiquidityPool = h.account(&lp.pool);
+    assert_eq!((pool.total_deposits, pool.queued_principal), (0, 0));
+    assert_eq!(h.account::<LiquidityProvider>(&lp.provider).balance, 0);
+}
+
+#[test]
+fn a_stuck_all_or_nothing_head_can_be_requeued_or_cancelled() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), 10 * SOL).unwrap();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    h.lp_deposit(&second, SOL).unwrap();
+    let lp = h.lp_accounts(&player.pubkey());
+    let second_lp = h.lp_accounts(&second.pubkey());
+    let shares = h.token_balance(&lp.depositor_lp);
+    let second_shares = h.token_balance(&second_lp.depositor_lp);
+
+    h.update_vault_config(DEFAULT_RESERVE_TARGET_LAMPORTS, 0, 10_000).unwrap();
+    h.play_slot(0, 35 * SOL / 10).1.unwrap();
+
+    let (stuck, result) = h.request_withdraw(&player, shares, false);
+    result.unwrap();
+    let (behind, result) = h.request_withdraw(&second, second_shares, false);
+    result.unwrap();
+    h.skip_withdraw_delay();
+    let code = custom_error(h.execute_withdraw(stuck, &player.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::WithdrawNotFillable));
+
+    // Anyone may move the stuck head to the back; the request behind it is served
+    let stranger = Keypair::new();
+    h.svm.airdrop(&stranger.pubkey(), SOL).unwrap();
+    let (requeued, result) = h.requeue_withdraw(&stranger, stuck);
+    result.unwrap();
+    assert!(h.is_closed(&h.withdraw_request(stuck)));
+    let request: WithdrawRequest = h.account(&h.withdraw_request(requeued));
+    assert_eq!((request.owner, request.shares), (player.pubkey(), shares));
+
+    let code = custom_error(h.requeue_withdraw(&stranger, behind).1);
+    assert_eq!(code, u32::from(CasinoError::NotRequeueable));
+    let second_before = h.balance(&second.pubkey());
+    h.execute_withdraw(behind, &second.pubkey()).unwrap();
+    assert!(h.balance(&second.pubkey()) > second_before);
+
+    // The owner can always take the shares back instead
+    h.cancel_withdraw(&player, requeued).unwrap();
+    assert!(h.is_closed(&h.withdraw_request(requeued)));
+    assert_eq!(h.token_balance(&lp.depositor_lp), shares);
+    assert_eq!(h.account::<LiquidityProvider>(&lp.provider).balance, 5 * SOL);
+    let pool: LiquidityPool = h.account(&lp.pool);
+    assert_eq!((pool.queue_head, pool.queue_tail), (3, 3));
+    assert_eq!((pool.queued_shares, pool.queued_principal), (0, 0));
+}
+
+#[test]
+fn cancelled_requests_behind_the_head_keep_their_place_until_served() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), 10 * SOL).unwrap();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    h.lp_deposit(&second, SOL).unwrap();
+    let second_lp = h.lp_accounts(&second.pubkey());
+    let second_shares = h.token_balance(&second_lp.depositor_lp);
+
+    let (first_id, result) = h.request_withdraw(&player, SOL, false);
+    result.unwrap();
+    let (second_id, result) = h.request_withdraw(&second, second_shares, true);
+    result.unwrap();
+
+    let code = custom_error(h.cancel_withdraw(&player, second_id));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+    h.cancel_withdraw(&second, second_id).unwrap();
+    assert_eq!(h.token_balance(&second_lp.depositor_lp), second_shares);
+    assert_eq!(h.account::<WithdrawRequest>(&h.withdraw_request(second_id)).shares, 0);
+    h.next_slot();
+    let code = custom_error(h.cancel_withdraw(&second, second_id));
+    assert_eq!(code, u32::from(CasinoError::WithdrawCancelled));
+
+    h.skip_withdraw_delay();
+    h.execute_withdraw(first_id, &player.pubkey()).unwrap();
+    h.execute_withdraw(second_id, &second.pubkey()).unwrap();
+    assert!(h.is_closed(&h.withdraw_request(second_id)));
+    assert_eq!(h.token_balance(&second_lp.depositor_lp), second_shares);
+    let pool: LiquidityPool = h.account(&second_lp.pool);
+    assert_eq!((pool.queue_head, pool.queue_tail, pool.queued_shares), (2, 2, 0));
+}
+
+#[test]
+fn withdrawals_wait_out_the_delay_and_are_served_in_order() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), 10 * SOL).unwrap();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    h.lp_deposit(&second, SOL).unwrap();
+
+    let (first_id, result) = h.request_withdraw(&player, SOL, false);
+    result.unwrap();
+    let (second_id, result) = h.request_withdraw(&second, SOL / 2, false);
+    result.unwrap();
+
+    let code = custom_error(h.execute_withdraw(first_id, &player.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::WithdrawalCooldown));
+
+    h.skip_withdraw_delay();
+    let code = custom_error(h.execute_withdraw(second_id, &second.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::NotQueueHead));
+    h.execute_withdraw(first_id, &player.pubkey()).unwrap();
+    h.execute_withdraw(second_id, &second.pubkey()).unwrap();
+
+    let pool: LiquidityPool = h.account(&h.lp_accounts(&player.pubkey()).pool);
+    assert_eq!((pool.queue_head, pool.queue_tail, pool.queued_shares), (2, 2, 0));
+}
+
+#[test]
+fn token_games_stake_and_pay_out_in_their_own_mint() {
+    let mut h = Harness::new();
+    let tokens = h.whitelist_mint(1_000 * TOKEN_BET, 100 * TOKEN_BET);
+    let casino_before = h.balance(&h.casino_vault);
+
+    let (game, state) = h.winning_token_coinflip(&tokens);
+    let plays = h.account::<MintConfig>(&tokens.mint_config).total_wagered / TOKEN_BET;
+    assert_eq!(state.mint, tokens.mint);
+    let win = calculate_win_payout(TOKEN_BET, 2, DEFAULT_HOUSE_EDGE_BPS).unwrap();
+    assert_eq!(state.payout_handle as u64, win);
+    assert_eq!(h.token_balance(&tokens.player_token), (100 - plays) * TOKEN_BET);
+
+    // SOL-side games can't pay out a token game
+    let code = custom_error(h.claim(game, state.payout_handle, state.payout_handle));
+    assert_eq!(code, u32::from(CasinoError::CurrencyMismatch));
+
+    let vault_before = h.token_balance(&tokens.token_vault);
+    h.claim_token(&tokens, game, state.payout_handle, state.payout_handle).unwrap();
+    assert!(h.is_closed(&game));
+    assert_eq!(h.token_balance(&tokens.token_vault), vault_before - state.payout_handle as u64);
+    assert_eq!(
+        h.token_balance(&tokens.player_token),
+        (100 - plays) * TOKEN_BET + state.payout_handle as u64
+    );
+
+    // Lamport vaults and exposure are untouched by token play
+    let config: MintConfig = h.account(&tokens.mint_config);
+    assert_eq!(config.total_paid_out, state.payout_handle as u64);
+    assert_eq!(config.open_games, plays - 1);
+    assert_eq!(h.balance(&h.casino_vault), casino_before);
+    assert_eq!(h.account::<Exposure>(&h.exposure).open_games, 0);
+}
+
+#[test]
+fn token_vaults_can_be_withdrawn_down_to_their_open_liability() {
+    let mut h = Harness::new();
+    let tokens = h.whitelist_mint(1_000 * TOKEN_BET, 100 * TOKEN_BET);
+    h.winning_token_coinflip(&tokens);
+    let outstanding = h.account::<MintConfig>(&tokens.mint_config).outstanding;
+    assert!(outstanding > 0);
+    let free = h.token_balance(&tokens.token_vault) - outstanding;
+    let player_before = h.token_balance(&tokens.player_token);
+
+    let player = h.player.insecure_clone();
+    let code = custom_error(h.withdraw_token_vault(&player, &tokens, free));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+    let house = h.house.insecure_clone();
+    let code = custom_error(h.withdraw_token_vault(&house, &tokens, free + 1));
+    assert_eq!(code, u32::from(CasinoError::LiabilityNotCovered));
+
+    h.withdraw_token_vault(&house, &tokens, free).unwrap();
+    assert_eq!(h.token_balance(&tokens.token_vault), outstanding);
+    assert_eq!(h.token_balance(&tokens.player_token), player_before + free);
+}
+
+#[test]
+fn token_bets_follow_the_mint_whitelist_and_limits() {
+    let mut h = Harness::new();
+    let tokens = h.whitelist_mint(10 * TOKEN_BET, 100 * TOKEN_BET);
+
+    let (_, result) = h.play_coinflip_token(&tokens, 0, 0, TOKEN_BET / 10 - 1);
+    assert_eq!(custom_error(result), u32::from(CasinoError::MinimumBet));
+
+    // A 5% exposure limit on a 10 token vault can't cover a 2x flip on 1 token
+    let (_, result) = h.play_coinflip_token(&tokens, 0, 0, TOKEN_BET);
+    assert_eq!(custom_error(result), u32::from(CasinoError::ExposureLimitExceeded));
+    h.play_coinflip_token(&tokens, 0, 0, TOKEN_BET / 10).1.unwrap();
+
+    h.update_mint_config(&tokens, TOKEN_BET / 10, 100 * TOKEN_BET, false).unwrap();
+    let (_, result) = h.play_coinflip_token(&tokens, 1, 0, TOKEN_BET / 10);
+    assert_eq!(custom_error(result), u32::from(CasinoError::MintNotAccepted));
+}
+
+#[test]
+fn confidential_games_settle_between_encrypted_balances() {
+    let mut h = Harness::new();
+    h.fund_house_balance(10 * SOL).unwrap();
+    h.deposit_balance(SOL).unwrap();
+    let balance = h.encrypted_balance(&h.player.pubkey());
+    assert_eq!(h.balance_handle(&balance), SOL as u128);
+    assert_eq!(h.balance(&h.balance_vault()), 11 * SOL);
+
+    let win = calculate_win_payout(BET, 2, DEFAULT_HOUSE_EDGE_BPS).unwrap() as u128;
+    let (mut staked, mut paid) = (0u128, 0u128);
+    for seed in 0..8u128 {
+        h.next_slot();
+        let (game, result) = h.play_confidential(seed, GameType::Coinflip, seed % 2, BET as u128);
+        result.unwrap();
+        let state: GameResult = h.account(&game);
+        assert!(state.confidential);
+        assert_eq!((state.bet_amount, state.max_payout), (0, 0));
+        let won = state.random_handles[0] == seed % 2;
+        assert_eq!(state.payout_handle, if won { win } else { 0 });
+        staked += BET as u128;
+        paid += state.payout_handle;
+        assert_eq!(h.balance_handle(&balance), SOL as u128 - staked + paid - state.payout_handle);
+
+        // Payouts never leave as lamports; they are credited to the balance
+        let code = custom_error(h.claim(game, state.payout_handle, state.payout_handle));
+        assert_eq!(code, u32::from(CasinoError::CurrencyMismatch));
+        h.claim_to_balance(game).unwrap();
+        assert!(h.is_closed(&game));
+        assert_eq!(h.balance_handle(&balance), SOL as u128 - staked + paid);
+    }
+    assert_eq!(h.balance_handle(&h.house_balance()), 10 * SOL as u128 + staked - paid);
+    assert_eq!(h.balance(&h.balance_vault()), 11 * SOL);
+    assert_eq!(h.account::<Exposure>(&h.exposure).open_games, 0);
+}
+
+#[test]
+fn invalid_confidential_stakes_play_for_nothing() {
+    let mut h = Harness::new();
+    h.fund_house_balance(BET).unwrap();
+    h.deposit_balance(10 * BET).unwrap();
+    let balance = h.encrypted_balance(&h.player.pubkey());
+
+    // Over the balance, below the minimum, and a jackpot the house balance can't cover
+    let stakes = [10 * BET as u128 + 1, DEFAULT_MIN_BET_LAMPORTS as u128 - 1, BET as u128];
+    for (seed, stake) in stakes.into_iter().enumerate() {
+        let (game, result) = h.play_confidential(seed as u128, GameType::Slot, 0, stake);
+        result.unwrap();
+        assert_eq!(h.account::<GameResult>(&game).payout_handle, 0);
+        assert_eq!(h.balance_handle(&balance), 10 * BET as u128);
+    }
+    assert_eq!(h.balance_handle(&h.house_balance()), BET as u128);
+}
+
+#[test]
+fn balance_withdrawals_pay_only_what_the_balance_covers() {
+    let mut h = Harness::new();
+    h.deposit_balance(SOL).unwrap();
+    let balance = h.encrypted_balance(&h.player.pubkey());
+
+    // Over the balance: nothing is debited and the decrypted outcome pays nothing
+    h.request_balance_withdraw(2 * SOL).unwrap();
+    let pending: EncryptedBalance = h.account(&balance);
+    assert_eq!((pending.pending_withdraw, pending.pending_handle), (2 * SOL, 0));
+    assert_eq!(pending.handle, SOL as u128);
+    let code = custom_error(h.request_balance_withdraw(SOL));
+    assert_eq!(code, u32::from(CasinoError::WithdrawPending));
+    h.complete_balance_withdraw(0, 0).unwrap();
+    assert_eq!(h.account::<EncryptedBalance>(&balance).pending_withdraw, 0);
+
+    // Covered: debited on request, paid once the outcome is verified
+    h.next_slot();
+    h.request_balance_withdraw(SOL / 2).unwrap();
+    assert_eq!(h.balance_handle(&balance), SOL as u128 / 2);
+    let code = custom_error(h.complete_balance_withdraw(0, 0));
+    assert_eq!(code, u32::from(CasinoError::InvalidDecryptionProof));
+    let player_before = h.balance(&h.player.pubkey());
+    h.complete_balance_withdraw(1, 1).unwrap();
+    assert!(h.balance(&h.player.pubkey()) > player_before + SOL / 2 - SOL / 1_000);
+    assert_eq!(h.balance(&h.balance_vault()), SOL / 2);
+
+    let code = custom_error(h.complete_balance_withdraw(1, 1));
+    assert_eq!(code, u32::from(CasinoError::NoPendingWithdraw));
+}
+
+#[test]
+fn approved_sermons_credit_faith_to_their_authors() {
+    let mut h = Harness::new();
+    let fee = h.account::<Admin>(&h.admin).quest_creation_fee;
+    let treasury_before = h.balance(&h.house.pubkey());
+    let (quest, result) = h.create_writing_quest(0, 100, 1);
+    result.unwrap();
+    assert_eq!(h.balance(&h.house.pubkey()), treasury_before + fee);
+
+    let (player, house) = (h.player.insecure_clone(), h.house.insecure_clone());
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), SOL).unwrap();
+    let (submission, result) = h.submit_sermon(&player, quest);
+    result.unwrap();
+    assert_eq!(h.account::<SermonSubmission>(&submission).content_hash, [9; 32]);
+    h.submit_sermon(&second, quest).1.unwrap();
+
+    // Only the curator judges, each sermon once, up to the quest's approvals
+    let code = custom_error(h.approve_submission(&player, quest, &player.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+    assert_eq!(h.faith_balance(&player.pubkey()), 100);
+    h.next_slot();
+    let code = custom_error(h.approve_submission(&house, quest, &player.pubkey()));
+    assert_eq!(code, u32::from(CasinoError::QuestFull));
+
+    let quest_state: WritingQuest = h.account(&quest);
+    assert_eq!((quest_state.submissions, quest_state.approvals), (2, 1));
+    assert!(h.is_closed(&h.profile(&second.pubkey())));
+}
+
+#[test]
+fn writing_quests_pay_for_all_the_faith_they_can_mint() {
+    let mut h = Harness::new();
+    let admin: Admin = h.account(&h.admin);
+    let treasury_before = h.balance(&h.house.pubkey());
+    let promo_before = h.balance(&h.promo_vault());
+    h.create_writing_quest(0, MAX_WRITING_QUEST_FAITH, 50).1.unwrap();
+
+    // The flat fee goes to the treasury, the FAITH's free-bet value to the promo budget
+    let backing = 50 * MAX_WRITING_QUEST_FAITH * FREE_BET_LAMPORTS_PER_FAITH;
+    assert_eq!(backing, 5 * SOL);
+    assert_eq!(h.balance(&h.house.pubkey()), treasury_before + admin.quest_creation_fee);
+    assert_eq!(h.balance(&h.promo_vault()), promo_before + backing);
+
+    // Unlimited approvals are priced out rather than free
+    assert!(h.create_writing_quest(1, MAX_WRITING_QUEST_FAITH, u32::MAX).1.is_err());
+}
+
+#[test]
+fn writing_quests_close_submissions_at_their_deadline() {
+    let mut h = Harness::new();
+    let (_, result) = h.create_writing_quest(0, MAX_WRITING_QUEST_FAITH + 1, 1);
+    assert_eq!(custom_error(result), u32::from(CasinoError::InvalidConfig));
+    let (quest, result) = h.create_writing_quest(1, MAX_WRITING_QUEST_FAITH, 1);
+    result.unwrap();
+
+    h.slot += 10;
+    h.next_slot();
+    let player = h.player.insecure_clone();
+    let (_, result) = h.submit_sermon(&player, quest);
+    assert_eq!(custom_error(result), u32::from(CasinoError::QuestExpired));
+}
+
+#[test]
+fn blink_quests_reward_verifier_attested_completions() {
+    let mut h = Harness::new();
+    let verifier = Keypair::new();
+    let house = h.house.insecure_clone();
+    h.set_quest_verifier(&verifier.pubkey(), true).unwrap();
+    let (quest, result) = h.create_blink_quest(&house, &verifier.pubkey(), 2);
+    result.unwrap();
+    assert_eq!(h.account::<BlinkQuest>(&quest).reward_pool, 2 * SOL / 10);
+
+    // Without an attestation, or one from another key, nothing is paid
+    let player = h.player.insecure_clone();
+    let code = custom_error(h.complete_blink_quest(&player, quest, None));
+    assert_eq!(code, u32::from(CasinoError::Ed25519Program));
+    let impostor = Keypair::new();
+    let code = custom_error(h.complete_blink_quest(&player, quest, Some(&impostor)));
+    assert_eq!(code, u32::from(CasinoError::Ed25519Pubkey));
+
+    // A verifier the authority disables stops attesting, even for quests that name it
+    h.set_quest_verifier(&verifier.pubkey(), false).unwrap();
+    let code = custom_error(h.complete_blink_quest(&player, quest, Some(&verifier)));
+    assert_eq!(code, u32::from(CasinoError::Unauthorized));
+    h.next_slot();
+    h.set_quest_verifier(&verifier.pubkey(), true).unwrap();
+
+    let pool_before = h.balance(&quest);
+    h.complete_blink_quest(&player, quest, Some(&verifier)).unwrap();
+    assert_eq!(h.faith_balance(&player.pubkey()), 50);
+    assert_eq!(h.balance(&quest), pool_before - SOL / 10);
+
+    // An attestation only covers the completer it names
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), SOL).unwrap();
+    let message = BlinkQuest::attestation_message(&quest, &player.pubkey());
+    let signature = verifier.sign_message(&message);
+    let ix = new_ed25519_instruction_with_signature(
+        &message,
+        signature.as_ref().try_into().unwrap(),
+        &verifier.pubkey().to_bytes(),
+    );
+    let complete = Instruction {
+        program_id: casino_n_church::ID,
+        accounts: casino_n_church::accounts::CompleteBlinkQuest {
+            completer: second.pubkey(),
+            admin: h.admin,
+            quest,
+            verifier_approval: h.quest_verifier(&verifier.pubkey()),
+            completion: pda(&[b"blink_completion", quest.as_ref(), second.pubkey().as_ref()]),
+            profile: h.profile(&second.pubkey()),
+            faith_mint: h.faith_mint(),
+            completer_faith: h.faith_account(&second.pubkey()),
+            instructions: sysvar::instructions::ID,
+            token_program: anchor_spl::token_2022::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: casino_n_church::instruction::CompleteBlinkQuest {}.data(),
+    };
+    let code = custom_error(h.send_all(vec![ix, complete], &second));
+    assert_eq!(code, u32::from(CasinoError::Ed25519Message));
+    h.complete_blink_quest(&second, quest, Some(&verifier)).unwrap();
+
+    // Full: the creator gets the account (and any unspent pool) back
+    let quest_state: BlinkQuest = h.account(&quest);
+    assert_eq!((quest_state.completions, quest_state.reward_pool), (2, 0));
+    let ix = Instruction {
+        program_id: casino_n_church::ID,
+        accounts: casino_n_church::accounts::CloseBlinkQuest { creator: h.house.pubkey(), quest }
+            .to_account_metas(None),
+        data: casino_n_church::instruction::CloseBlinkQuest {}.data(),
+    };
+    h.send_as_house(ix).unwrap();
+    assert!(h.is_closed(&quest));
+}
+
+#[test]
+fn blink_quests_pay_for_all_the_faith_they_can_mint() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    // Creators can't attest their own completions, only name an approved verifier
+    let (_, result) = h.create_blink_quest(&player, &player.pubkey(), 10);
+    assert!(result.is_err());
+    let verifier = Keypair::new();
+    h.set_quest_verifier(&verifier.pubkey(), true).unwrap();
+    let fee = h.account::<Admin>(&h.admin).quest_creation_fee;
+    let treasury_before = h.balance(&h.house.pubkey());
+    let promo_before = h.balance(&h.promo_vault());
+    let (quest, result) = h.create_blink_quest(&player, &verifier.pubkey(), 10);
+    result.unwrap();
+
+    // The flat fee goes to the treasury and every FAITH's free-bet value to the promo budget
+    let backing = 10 * 50 * FREE_BET_LAMPORTS_PER_FAITH;
+    assert_eq!(h.balance(&h.house.pubkey()), treasury_before + fee);
+    assert_eq!(h.balance(&h.promo_vault()), promo_before + backing);
+    assert_eq!(h.account::<BlinkQuest>(&quest).reward_pool, SOL);
+}
+
+#[test]
+fn luck_and_faith_decay_while_the_other_realm_is_neglected() {
+    let mut h = Harness::new();
+    let (player, house) = (h.player.insecure_clone(), h.house.insecure_clone());
+    let profile = h.profile(&player.pubkey());
+    let (quest, result) = h.create_writing_quest(0, 100, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+
+    // Every bet earns LUCK and a winning claim earns more
+    let (game, state) = h.winning_coinflip();
+    let played = h.account::<PlayerProfile>(&profile);
+    assert!(played.luck >= LUCK_PER_PLAY);
+    h.claim(game, state.payout_handle, state.payout_handle).unwrap();
+    let before = h.account::<PlayerProfile>(&profile);
+    assert_eq!(before.luck, played.luck + LUCK_PER_WIN);
+    assert_eq!(h.faith_balance(&player.pubkey()), 100);
+
+    // Two full periods without the Church cost LUCK two decay steps; FAITH, whose
+    // Casino grace period started later, only one
+    h.slot = before.last_church_slot + REALM_GRACE_SLOTS + 2 * REALM_DECAY_PERIOD_SLOTS - 1;
+    h.next_slot();
+    let (quest, result) = h.create_writing_quest(1, 100, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+    let after = h.account::<PlayerProfile>(&profile);
+    assert_eq!(after.luck, decay_points(before.luck, 2));
+    assert_eq!(h.faith_balance(&player.pubkey()), decay_points(100, 1) + 100);
+    assert_eq!(after.last_church_slot, h.slot);
+}
+
+#[test]
+fn faith_redeems_for_vouchers_that_boost_the_next_bet() {
+    let mut h = Harness::new();
+    let (player, house) = (h.player.insecure_clone(), h.house.insecure_clone());
+    let (quest, result) = h.create_writing_quest(0, MAX_WRITING_QUEST_FAITH, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+    let boost = h.boost(&player.pubkey());
+
+    // Free bets are paid from the promo budget, escrowed in the voucher when redeemed.
+    // A budget other free bets have already drained must be topped up first.
+    let free_bet = DEFAULT_MIN_BET_LAMPORTS;
+    let mut promo = h.svm.get_account(&h.promo_vault()).unwrap();
+    promo.lamports = h.svm.minimum_balance_for_rent_exemption(0);
+    h.svm.set_account(h.promo_vault(), promo).unwrap();
+    let code = custom_error(h.redeem_faith(BoostKind::FreeBet, free_bet));
+    assert_eq!(code, u32::from(CasinoError::InsufficientPromoBudget));
+    h.fund_promo_budget(SOL).unwrap();
+    let promo_before = h.balance(&h.promo_vault());
+    h.redeem_faith(BoostKind::FreeBet, free_bet).unwrap();
+    let faith = MAX_WRITING_QUEST_FAITH - free_bet / FREE_BET_LAMPORTS_PER_FAITH;
+    assert_eq!(h.faith_balance(&player.pubkey()), faith);
+    assert_eq!(h.balance(&h.promo_vault()), promo_before - free_bet);
+    h.next_slot();
+    assert!(h.redeem_faith(BoostKind::EdgeDiscount, 10).is_err());
+
+    // The voucher's credit is the whole stake
+    let code = custom_error(h.play_coinflip(0, 0, free_bet * 2).1);
+    assert_eq!(code, u32::from(CasinoError::InvalidBoost));
+    let vault_before = h.balance(&h.casino_vault);
+    let (game, result) = h.play_coinflip(0, 0, free_bet);
+    result.unwrap();
+    assert_eq!(h.balance(&h.casino_vault), vault_before + free_bet);
+    assert_eq!(h.account::<GameResult>(&game).bet_amount, free_bet);
+    assert!(h.is_closed(&boost));
+
+    // An edge discount waives the house edge on the next bet only
+    let edge = h.account::<GameConfig>(&h.game_config(GAME_ID_COINFLIP)).house_edge_bps;
+    h.redeem_faith(BoostKind::EdgeDiscount, edge as u64).unwrap();
+    let (game, result) = h.play_coinflip(1, 0, BET);
+    result.unwrap();
+    assert_eq!(h.account::<GameResult>(&game).max_payout, calculate_win_payout(BET, 2, 0).unwrap());
+    let (game, result) = h.play_coinflip(2, 0, BET);
+    result.unwrap();
+    let full_edge = calculate_win_payout(BET, 2, edge).unwrap();
+    assert_eq!(h.account::<GameResult>(&game).max_payout, full_edge);
+
+    // A payout multiplier scales the stake that payouts are computed on
+    let bonus_bps = 300;
+    h.redeem_faith(BoostKind::PayoutMultiplier, bonus_bps).unwrap();
+    let spent = edge as u64 * FAITH_PER_EDGE_DISCOUNT_BPS + bonus_bps * FAITH_PER_MULTIPLIER_BPS;
+    let faith = faith - spent;
+    assert_eq!(h.faith_balance(&player.pubkey()), faith);
+    let (game, result) = h.play_coinflip(3, 0, BET);
+    result.unwrap();
+    let boosted = calculate_win_payout(BET + BET * bonus_bps / 10_000, 2, edge).unwrap();
+    assert_eq!(h.account::<GameResult>(&game).max_payout, boosted);
+
+    let code = custom_error(h.redeem_faith(BoostKind::PayoutMultiplier, MAX_BOOST_MULTIPLIER_BPS));
+    assert_eq!(code, u32::from(CasinoError::InsufficientFaith));
+}
+
+#[test]
+fn stake_boosts_are_priced_per_unit_of_stake_they_cover() {
+    let mut h = Harness::new();
+    let (player, house) = (h.player.insecure_clone(), h.house.insecure_clone());
+    let (quest, result) = h.create_writing_quest(0, MAX_WRITING_QUEST_FAITH, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+
+    // A voucher only boosts bets up to the stake it was bought for
+    let code = custom_error(h.redeem_faith_up_to(BoostKind::EdgeDiscount, 10, 0));
+    assert_eq!(code, u32::from(CasinoError::InvalidBoost));
+    let code = custom_error(h.redeem_faith_up_to(BoostKind::FreeBet, BET, 2 * BET));
+    assert_eq!(code, u32::from(CasinoError::InvalidBoost));
+    h.redeem_faith_up_to(BoostKind::PayoutMultiplier, 100, BET).unwrap();
+    let faith = MAX_WRITING_QUEST_FAITH - 100 * FAITH_PER_MULTIPLIER_BPS;
+    assert_eq!(h.faith_balance(&player.pubkey()), faith);
+    let code = custom_error(h.play_coinflip(0, 0, 2 * BET).1);
+    assert_eq!(code, u32::from(CasinoError::InvalidBoost));
+    h.play_coinflip(0, 0, BET).1.unwrap();
+
+    // Covering more stake costs proportionally more FAITH
+    h.next_slot();
+    h.redeem_faith_up_to(BoostKind::EdgeDiscount, 10, 2 * BOOST_STAKE_UNIT_LAMPORTS + 1).unwrap();
+    let faith = faith - 3 * 10 * FAITH_PER_EDGE_DISCOUNT_BPS;
+    assert_eq!(h.faith_balance(&player.pubkey()), faith);
+    assert_eq!(h.account::<Boost>(&h.boost(&player.pubkey())).max_stake, 2 * SOL + 1);
+}
+
+#[test]
+fn unplayable_free_bets_can_be_cancelled_back_into_the_promo_budget() {
+    let mut h = Harness::new();
+    let (player, house) = (h.player.insecure_clone(), h.house.insecure_clone());
+    let (quest, result) = h.create_writing_quest(0, MAX_WRITING_QUEST_FAITH, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+    h.fund_promo_budget(SOL).unwrap();
+    let promo_before = h.balance(&h.promo_vault());
+
+    // A credit the coinflip config no longer accepts can't be played...
+    let free_bet = 2 * DEFAULT_MIN_BET_LAMPORTS;
+    h.redeem_faith(BoostKind::FreeBet, free_bet).unwrap();
+    let config: GameConfig = h.account(&h.game_config(GAME_ID_COINFLIP));
+    h.update_game_config(
+        GAME_ID_COINFLIP,
+        GameConfigParams {
+            min_bet: 3 * DEFAULT_MIN_BET_LAMPORTS,
+            max_bet: config.max_bet,
+            house_edge_bps: config.house_edge_bps,
+            straight_multiplier: config.straight_multiplier,
+            jackpot_multiplier: config.jackpot_multiplier,
+            small_win_multiplier: config.small_win_multiplier,
+            max_multiplier_bps: config.max_multiplier_bps,
+            paused: config.paused,
+        },
+    )
+    .unwrap();
+    let code = custom_error(h.play_coinflip(0, 0, free_bet).1);
+    assert_eq!(code, u32::from(CasinoError::MinimumBet));
+
+    // ...so its owner drops it, and the credit goes back to the budget
+    let boost = h.boost(&player.pubkey());
+    let rent = h.balance(&boost) - free_bet;
+    let player_before = h.balance(&player.pubkey());
+    h.cancel_boost(&player).unwrap();
+    assert!(h.is_closed(&boost));
+    assert_eq!(h.balance(&h.promo_vault()), promo_before);
+    assert_eq!(h.balance(&player.pubkey()), player_before + rent - 5_000);
+
+    h.next_slot();
+    h.redeem_faith(BoostKind::EdgeDiscount, 10).unwrap();
+    h.cancel_boost(&player).unwrap();
+    assert_eq!(h.balance(&h.promo_vault()), promo_before);
+}
+
+#[test]
+fn faith_is_a_transferable_token_only_its_holder_can_burn() {
+    let mut h = Harness::new();
+    let (player, house) = (h.player.insecure_clone(), h.house.insecure_clone());
+    let (quest, result) = h.create_writing_quest(0, 100, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+
+    // Only the admin PDA mints FAITH, and no delegate can move or burn it behind the holder
+    let token_2022 = anchor_spl::token_2022::ID;
+    let mint_account = h.svm.get_account(&h.faith_mint()).unwrap();
+    assert_eq!(mint_account.owner, token_2022);
+    let mint =
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+    assert_eq!(mint.base.mint_authority, COption::Some(h.admin));
+    assert_eq!((mint.base.supply, mint.base.decimals), (100, FAITH_DECIMALS));
+    assert!(mint.get_extension::<PermanentDelegate>().is_err());
+
+    // Holders move FAITH like any other token
+    let friend = Keypair::new();
+    let create = create_associated_token_account(
+        &player.pubkey(),
+        &friend.pubkey(),
+        &h.faith_mint(),
+        &token_2022,
+    );
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &token_2022,
+        &h.faith_account(&player.pubkey()),
+        &h.faith_mint(),
+        &h.faith_account(&friend.pubkey()),
+        &player.pubkey(),
+        &[],
+        95,
+        FAITH_DECIMALS,
+    )
+    .unwrap();
+    h.send_all(vec![create, transfer], &player).unwrap();
+    assert_eq!(h.faith_balance(&friend.pubkey()), 95);
+
+    // Decay for neglecting the Casino is charged on the 100 FAITH the player held, not the
+    // 5 left after moving it out. What the balance can't cover is withheld from the next
+    // reward, and the FAITH the friend received isn't charged at all.
+    h.slot += REALM_GRACE_SLOTS + REALM_DECAY_PERIOD_SLOTS;
+    h.next_slot();
+    let owed = 100 - decay_points(100, 1);
+    let (quest, result) = h.create_writing_quest(1, 100, 1);
+    result.unwrap();
+    h.submit_sermon(&player, quest).1.unwrap();
+    h.approve_submission(&house, quest, &player.pubkey()).unwrap();
+    assert_eq!(h.faith_balance(&player.pubkey()), 5 + 100 - owed);
+    assert_eq!(h.faith_balance(&friend.pubkey()), 95);
+    let profile = h.account::<PlayerProfile>(&h.profile(&player.pubkey()));
+    assert_eq!((profile.faith_held, profile.faith_decay_owed), (5 + 100 - owed, 0));
+
+    // Another period later, redeeming burns the decay on that balance from the holder
+    h.slot += REALM_DECAY_PERIOD_SLOTS;
+    h.next_slot();
+    h.redeem_faith(BoostKind::EdgeDiscount, 1).unwrap();
+    let kept = decay_points(5 + 100 - owed, 1) - FAITH_PER_EDGE_DISCOUNT_BPS;
+    assert_eq!(h.faith_balance(&player.pubkey()), kept);
+    assert_eq!(h.account::<PlayerProfile>(&h.profile(&player.pubkey())).faith_held, kept);
+}
+
+#[test]
+fn legacy_admin_accounts_migrate_in_place() {
+    let mut h = Harness::new();
+    let authority = Keypair::new();
+    h.svm.airdrop(&authority.pubkey(), SOL).unwrap();
+    let (admin, bump) = Pubkey::find_program_address(
+        &[b"admin", authority.pubkey().as_ref()],
+        &casino_n_church::ID,
+    );
+    let legacy = AdminV0 {
+        authority: authority.pubkey(),
+        treasury: h.house.pubkey(),
+        casino_vault: pda(&[b"casino_vault", admin.as_ref()]),
+        lp_vault: pda(&[b"lp_vault", admin.as_ref()]),
+        house_edge_bps: 300,
+        quest_creation_fee: SOL / 20,
+        bump,
+    };
+    let mut data = Admin::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    let account = Account {
+        lamports: h.svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: casino_n_church::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    h.svm.set_account(admin, account).unwrap();
+
+    let ix = Instruction {
+        program_id: casino_n_church::ID,
+        accounts: casino_n_church::accounts::MigrateAdmin {
+            authority: authority.pubkey(),
+            admin,
+            exposure: pda(&[b"exposure", admin.as_ref()]),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: casino_n_church::instruction::MigrateAdmin {}.data(),
+    };
+    h.send(ix.clone(), &authority).unwrap();
+
+    // Legacy settings survive; everything added since starts at its default
+    let migrated: Admin = h.account(&admin);
+    assert_eq!(migrated.treasury, h.house.pubkey());
+    assert_eq!((migrated.house_edge_bps, migrated.quest_creation_fee), (300, SOL / 20));
+    assert_eq!((migrated.guardian, migrated.bump), (authority.pubkey(), bump));
+    assert_eq!(migrated.max_bet, DEFAULT_MAX_BET_LAMPORTS);
+    assert_eq!(migrated.max_exposure_bps, DEFAULT_MAX_EXPOSURE_BPS);
+    assert_eq!(h.account::<Exposure>(&pda(&[b"exposure", admin.as_ref()])).admin, admin);
+
+    h.next_slot();
+    assert!(h.send(ix, &authority).is_err());
+}
+
+#[test]
+fn bets_below_minimum_are_rejected() {
+    let mut h = Harness::new();
+    let (_, result) = h.play_coinflip(0, 0, DEFAULT_MIN_BET_LAMPORTS - 1);
+    assert_eq!(custom_error(result), u32::from(CasinoError::MinimumBet));
+}
+
+#[test]
+fn paused_games_reject_play() {
+    let mut h = Harness::new();
+    let ix = Instruction {
+        program_id: casino_n_church::ID,
+        accounts: casino_n_church::accounts::SetPause { admin: h.admin, signer: h.house.pubkey() }
+            .to_account_metas(None),
+        data: casino_n_church::instruction::SetPause { paused: false, pause_flags: PAUSE_GAMES }
+            .data(),
+    };
+    h.send_as_house(ix).unwrap();
+
+    let (_, result) = h.play_coinflip(0, 0, BET);
+    assert_eq!(custom_error(result), u32::from(CasinoError::ProtocolPaused));
+}
+
+#[test]
+fn the_first_lp_cannot_claim_the_house_seed() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    let lp = h.lp_accounts(&player.pubkey());
+
+    // The initialize_vault seed backs shares nobody holds
+    let seed = h.pool_assets();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    assert_eq!(h.token_balance(&lp.depositor_lp), 5 * SOL);
+    assert_eq!(h.account::<LiquidityPool>(&lp.pool).unowned_shares, seed);
+
+    // Redeeming every share takes the deposit out and leaves the seed behind
+    let (id, result) = h.request_withdraw(&player, 5 * SOL, false);
+    result.unwrap();
+    h.skip_withdraw_delay();
+    h.execute_withdraw(id, &player.pubkey()).unwrap();
+    assert_eq!(h.pool_assets(), seed);
+    assert_eq!(h.account::<Mint>(&lp.lp_mint).supply, 0);
+
+    // An emptied pool prices the next deposit 1:1 again
+    h.lp_deposit(&player, SOL).unwrap();
+    assert_eq!(h.token_balance(&lp.depositor_lp), SOL);
+}
+
+#[test]
+fn lp_shares_are_minted_and_burned_at_nav() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    let lp = h.lp_accounts(&player.pubkey());
+
+    // First deposit mints one share per lamport
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+    assert_eq!(h.token_balance(&lp.depositor_lp), 5 * SOL);
+    assert_eq!(h.account::<LiquidityProvider>(&lp.provider).balance, 5 * SOL);
+
+    // A second LP is priced at the current NAV
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), 10 * SOL).unwrap();
+    let (assets, supply) = (h.pool_assets(), h.priced_supply());
+    h.lp_deposit(&second, SOL).unwrap();
+    let second_lp = h.lp_accounts(&second.pubkey()).depositor_lp;
+    assert_eq!(h.token_balance(&second_lp), shares_for_deposit(SOL, assets, supply).unwrap());
+
+    // Requested shares are escrowed, then burned at NAV when the request is executed
+    h.next_slot();
+    let (id, result) = h.request_withdraw(&player, 2 * SOL, false);
+    result.unwrap();
+    assert_eq!(h.token_balance(&lp.depositor_lp), 3 * SOL);
+    assert_eq!(h.token_balance(&lp.escrow), 2 * SOL);
+    assert_eq!(h.account::<LiquidityProvider>(&lp.provider).balance, 3 * SOL);
+
+    h.skip_withdraw_delay();
+    let (assets, supply) = (h.pool_assets(), h.priced_supply());
+    let vault_before = h.balance(&h.lp_vault);
+    h.execute_withdraw(id, &player.pubkey()).unwrap();
+    assert_eq!(h.token_balance(&lp.escrow), 0);
+    assert_eq!(h.priced_supply(), supply - 2 * SOL);
+    let redeemed = vault_before - h.balance(&h.lp_vault);
+    assert_eq!(redeemed, lamports_for_shares(2 * SOL, assets, supply).unwrap());
+
+    // Cannot queue more shares than held
+    let (_, result) = h.request_withdraw(&player, 3 * SOL + 1, false);
+    assert_eq!(custom_error(result), u32::from(CasinoError::MaximumBet));
+}
+
+#[test]
+fn deposits_are_priced_net_of_what_open_games_could_take_from_lps() {
+    let mut h = Harness::new();
+    let player = h.player.insecure_clone();
+    h.lp_deposit(&player, 5 * SOL).unwrap();
+
+    // A jackpot bigger than the house's own float would be paid partly from LP capital
+    h.update_vault_config(DEFAULT_RESERVE_TARGET_LAMPORTS, 0, 10_000).unwrap();
+    h.play_slot(0, 35 * SOL / 10).1.unwrap();
+    let outstanding = h.account::<Exposure>(&h.exposure).outstanding;
+    let liability = lp_exposure(outstanding, h.casino_float(), h.lp_float());
+    assert!(liability > 0);
+
+    // The next LP buys in as if it were paid, so they get more shares than at gross NAV
+    let second = Keypair::new();
+    h.svm.airdrop(&second.pubkey(), 10 * SOL).unwrap();
+    let (assets, supply) = (h.pool_assets(), h.priced_supply());
+    h.lp_deposit(&second, SOL).unwrap();
+    let shares = h.token_balance(&h.lp_accounts(&second.pubkey()).depositor_lp);
+    assert_eq!(shares, shares_for_deposit(SOL, assets - liability, supply).unwrap());
+    assert!(shares > shares_for_deposit(SOL, assets, supply).unwrap());
+}
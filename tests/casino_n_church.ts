@@ -88,6 +88,19 @@ describe("Casino & Church - Inco Privacy Tests", () => {
     );
   }
 
+  // Game ids seeding the per-game config PDAs (match GameType order)
+  const GAME_ID = { roulette: 0, coinflip: 1, slot: 2, aviator: 3 };
+
+  // Public roulette bet types (encrypted choice is read per type)
+  const ROULETTE_BET = { straight: 0, color: 1, oddEven: 2, highLow: 3, dozen: 4, column: 5, split: 6 };
+
+  function deriveGameConfigPda(gameId: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("game_config"), adminPda.toBuffer(), Buffer.from([gameId])],
+      program.programId
+    )[0];
+  }
+
   function seedToBuffer(seed: bigint): Buffer {
     const buf = Buffer.alloc(16);
     let v = seed;
@@ -271,6 +284,15 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         // Check if already initialized
         const adminInfo = await connection.getAccountInfo(adminPda);
         if (adminInfo) {
+          // Admins created by an earlier deployment are upgraded to the current layout
+          if (adminInfo.data.length < program.account.admin.size) {
+            const tx = await program.methods
+              .migrateAdmin()
+              .accounts({ authority: wallet.publicKey, admin: adminPda } as any)
+              .rpc();
+            console.log("   Admin migrated:", tx);
+            return;
+          }
           console.log("   Admin already initialized, skipping...");
           return;
         }
@@ -305,6 +327,34 @@ describe("Casino & Church - Inco Privacy Tests", () => {
     });
   });
 
+  describe("1b. Initialize Game Configs", () => {
+    it("should create a config PDA for every game", async () => {
+      for (const [name, gameId] of Object.entries(GAME_ID)) {
+        const gameConfigPda = deriveGameConfigPda(gameId);
+        const info = await connection.getAccountInfo(gameConfigPda);
+        if (info) {
+          console.log(`   ${name} config already initialized, skipping...`);
+          continue;
+        }
+
+        const tx = await program.methods
+          .initGameConfig(gameId)
+          .accounts({
+            authority: wallet.publicKey,
+            admin: adminPda,
+            gameConfig: gameConfigPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .rpc();
+        console.log(`   ${name} config initialized:`, tx);
+
+        const config = await program.account.gameConfig.fetch(gameConfigPda);
+        expect(config.gameId).to.equal(gameId);
+        expect(config.paused).to.be.false;
+      }
+    });
+  });
+
   // ============ COINFLIP TESTS ============
 
   describe("2. Coinflip Game", () => {
@@ -337,6 +387,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         .accounts({
           player: wallet.publicKey,
           admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.coinflip),
           casinoVault: casinoVaultPda,
           game: gamePda,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -361,6 +412,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         .accounts({
           player: wallet.publicKey,
           admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.coinflip),
           casinoVault: casinoVaultPda,
           game: gamePda,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -434,12 +486,14 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         const txForSim = await program.methods
           .playRoulette(
             new BN(rouletteSeed.toString()),
+            ROULETTE_BET.straight,
             hexToBuffer(encryptedChoice),
             new BN(BET_AMOUNT)
           )
           .accounts({
             player: wallet.publicKey,
             admin: adminPda,
+            gameConfig: deriveGameConfigPda(GAME_ID.roulette),
             casinoVault: casinoVaultPda,
             game: gamePda,
             incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -456,12 +510,14 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         const tx = await program.methods
           .playRoulette(
             new BN(rouletteSeed.toString()),
+            ROULETTE_BET.straight,
             hexToBuffer(encryptedChoice),
             new BN(BET_AMOUNT)
           )
           .accounts({
             player: wallet.publicKey,
             admin: adminPda,
+            gameConfig: deriveGameConfigPda(GAME_ID.roulette),
             casinoVault: casinoVaultPda,
             game: gamePda,
             incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -500,6 +556,151 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         }
       });
     });
+
+    describe("3b. Color Bet (red/black)", () => {
+      const rouletteSeed = BigInt(baseTimestamp + 101);
+      let gamePda: PublicKey;
+
+      before(() => {
+        [gamePda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("game_roulette"),
+            wallet.publicKey.toBuffer(),
+            seedToBuffer(rouletteSeed),
+          ],
+          program.programId
+        );
+      });
+
+      it("Play roulette color bet on red", async () => {
+        console.log("   Encrypting choice: RED (0)");
+        const encryptedChoice = await encryptValue(BigInt(0)); // Red = 0, Black = 1
+
+        const accounts = {
+          player: wallet.publicKey,
+          admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.roulette),
+          casinoVault: casinoVaultPda,
+          game: gamePda,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        };
+
+        // Step 1: Simulate to get handles
+        const txForSim = await program.methods
+          .playRoulette(
+            new BN(rouletteSeed.toString()),
+            ROULETTE_BET.color,
+            hexToBuffer(encryptedChoice),
+            new BN(BET_AMOUNT)
+          )
+          .accounts(accounts as any)
+          .transaction();
+
+        const { payoutHandle: simPayoutHandle, randomHandles: simRandomHandles } = await getHandlesFromSimulation(txForSim);
+        const remainingAccounts = buildAllowanceAccounts(simPayoutHandle, simRandomHandles);
+
+        // Step 2: Execute with remaining accounts
+        const tx = await program.methods
+          .playRoulette(
+            new BN(rouletteSeed.toString()),
+            ROULETTE_BET.color,
+            hexToBuffer(encryptedChoice),
+            new BN(BET_AMOUNT)
+          )
+          .accounts(accounts as any)
+          .remainingAccounts(remainingAccounts)
+          .rpc();
+
+        console.log("   Roulette color bet placed:", tx);
+
+        const gameAccount = await program.account.gameResult.fetch(gamePda);
+        expect(gameAccount.gameType.roulette?.betType).to.equal(ROULETTE_BET.color);
+
+        const payoutResult = await decryptHandle(gameAccount.payoutHandle.toString());
+        const spinResult = await decryptHandle(gameAccount.randomHandles[0].toString());
+        if (payoutResult && spinResult) {
+          const payout = parseInt(payoutResult.plaintext, 10);
+          const spin = parseInt(spinResult.plaintext, 10);
+          const RED = [1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36];
+          console.log("   Decrypted spin:", spin, RED.includes(spin) ? "🔴 RED" : spin === 0 ? "🟢 GREEN" : "⚫ BLACK");
+          console.log("   Decrypted payout:", payout, "lamports");
+          expect(payout > 0).to.equal(RED.includes(spin));
+        } else {
+          console.log("   ❌ Could not decrypt color bet result");
+        }
+      });
+    });
+
+    describe("3c. Multi-bet spin (straight + split + color)", () => {
+      const rouletteSeed = BigInt(baseTimestamp + 102);
+      let gamePda: PublicKey;
+      let legsPda: PublicKey;
+
+      before(() => {
+        [gamePda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("game_roulette"),
+            wallet.publicKey.toBuffer(),
+            seedToBuffer(rouletteSeed),
+          ],
+          program.programId
+        );
+        [legsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("roulette_legs"), gamePda.toBuffer()],
+          program.programId
+        );
+      });
+
+      it("Play three legs on one spin", async () => {
+        const legs = [
+          { betType: ROULETTE_BET.straight, encryptedChoice: hexToBuffer(await encryptValue(BigInt(17))), amount: new BN(BET_AMOUNT) },
+          { betType: ROULETTE_BET.split, encryptedChoice: hexToBuffer(await encryptValue(BigInt(17 * 37 + 20))), amount: new BN(BET_AMOUNT) },
+          { betType: ROULETTE_BET.color, encryptedChoice: hexToBuffer(await encryptValue(BigInt(1))), amount: new BN(BET_AMOUNT) },
+        ];
+
+        const accounts = {
+          player: wallet.publicKey,
+          admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.roulette),
+          casinoVault: casinoVaultPda,
+          game: gamePda,
+          legs: legsPda,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        };
+
+        const txForSim = await program.methods
+          .playRouletteMulti(new BN(rouletteSeed.toString()), legs)
+          .accounts(accounts as any)
+          .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+          .transaction();
+
+        const { payoutHandle: simPayoutHandle, randomHandles: simRandomHandles } = await getHandlesFromSimulation(txForSim);
+        const remainingAccounts = buildAllowanceAccounts(simPayoutHandle, simRandomHandles);
+
+        const tx = await program.methods
+          .playRouletteMulti(new BN(rouletteSeed.toString()), legs)
+          .accounts(accounts as any)
+          .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+          .remainingAccounts(remainingAccounts)
+          .rpc();
+
+        console.log("   Multi-bet spin placed:", tx);
+
+        const gameAccount = await program.account.gameResult.fetch(gamePda);
+        const legsAccount = await program.account.rouletteSpinLegs.fetch(legsPda);
+        expect(gameAccount.betAmount.toNumber()).to.equal(BET_AMOUNT * legs.length);
+        expect(legsAccount.legs.length).to.equal(legs.length);
+
+        const payoutResult = await decryptHandle(gameAccount.payoutHandle.toString());
+        if (payoutResult) {
+          console.log("   Decrypted total payout:", parseInt(payoutResult.plaintext, 10), "lamports");
+        } else {
+          console.log("   ❌ Could not decrypt payout");
+        }
+      });
+    });
   });
 
 
@@ -529,6 +730,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         .accounts({
           player: wallet.publicKey,
           admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.slot),
           casinoVault: casinoVaultPda,
           game: gamePda,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -547,6 +749,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         .accounts({
           player: wallet.publicKey,
           admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.slot),
           casinoVault: casinoVaultPda,
           game: gamePda,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -617,6 +820,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
     it("Play aviator with 2x target multiplier", async () => {
       // Target 2x multiplier = 20000 BPS
       const targetMultiplierBps = BigInt(20000);
+      // The target stays encrypted; the cap (set to the target here) is public
       console.log("   Target multiplier: 2.00x (20000 BPS)");
       console.log("   Encrypting target multiplier...");
 
@@ -627,11 +831,13 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         .playAviator(
           new BN(aviatorSeed.toString()),
           hexToBuffer(encryptedTarget),
+          Number(targetMultiplierBps),
           new BN(BET_AMOUNT)
         )
         .accounts({
           player: wallet.publicKey,
           admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.aviator),
           casinoVault: casinoVaultPda,
           game: gamePda,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -649,11 +855,13 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         .playAviator(
           new BN(aviatorSeed.toString()),
           hexToBuffer(encryptedTarget),
+          Number(targetMultiplierBps),
           new BN(BET_AMOUNT)
         )
         .accounts({
           player: wallet.publicKey,
           admin: adminPda,
+          gameConfig: deriveGameConfigPda(GAME_ID.aviator),
           casinoVault: casinoVaultPda,
           game: gamePda,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -697,6 +905,82 @@ describe("Casino & Church - Inco Privacy Tests", () => {
   // ============ CLAIM REWARDS TEST ============
 
   describe("6. Claim Rewards", () => {
+    it("Should reject claim with another game's payout handle", async () => {
+      // Decrypt the slot payout, then try to claim it against the coinflip game
+      const [slotGamePda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("game_slot"),
+          wallet.publicKey.toBuffer(),
+          seedToBuffer(BigInt(baseTimestamp + 200)),
+        ],
+        program.programId
+      );
+      const [coinflipGamePda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("game_coinflip"),
+          wallet.publicKey.toBuffer(),
+          seedToBuffer(BigInt(baseTimestamp)),
+        ],
+        program.programId
+      );
+
+      let slotGame;
+      try {
+        slotGame = await program.account.gameResult.fetch(slotGamePda);
+        await program.account.gameResult.fetch(coinflipGamePda);
+      } catch {
+        console.log("   Games not found, skipping foreign handle test");
+        return;
+      }
+
+      const foreignHandle = slotGame.payoutHandle.toString();
+      const result = await decryptHandle(foreignHandle);
+      if (!result) {
+        console.log("   Could not decrypt, skipping foreign handle test");
+        return;
+      }
+
+      const claimIx = await program.methods
+        .claimRewards(handleToBuffer(foreignHandle), plaintextToBuffer(result.plaintext))
+        .accounts({
+          player: wallet.publicKey,
+          admin: adminPda,
+          casinoVault: casinoVaultPda,
+          lpVault: lpVaultPda,
+          game: coinflipGamePda,
+          legs: null,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .instruction();
+
+      const tx = new Transaction();
+      result.ed25519Instructions.forEach((ix) => tx.add(ix));
+      tx.add(claimIx);
+
+      const { blockhash } = await connection.getLatestBlockhash();
+      tx.recentBlockhash = blockhash;
+      tx.feePayer = wallet.publicKey;
+
+      try {
+        const signedTx = await provider.wallet.signTransaction(tx);
+        const sig = await connection.sendRawTransaction(signedTx.serialize());
+        await connection.confirmTransaction(sig, "confirmed");
+        throw new Error("Should have failed!");
+      } catch (e: any) {
+        const logs: string[] = e.logs || [];
+        const rejected =
+          e.message?.includes("PayoutHandleMismatch") ||
+          logs.some((log) => log.includes("PayoutHandleMismatch"));
+        expect(rejected, e.message).to.be.true;
+        console.log("   ✅ Correctly rejected foreign payout handle");
+      }
+
+      const coinflipGame = await program.account.gameResult.fetch(coinflipGamePda);
+      expect(coinflipGame.claimed).to.be.false;
+    });
+
     it("Claim rewards from slot machine win", async () => {
       // Use the slot machine game which has the highest payout
       const slotSeed = BigInt(baseTimestamp + 200);
@@ -737,7 +1021,35 @@ describe("Casino & Church - Inco Privacy Tests", () => {
       console.log("   Decrypted payout:", payout, "lamports");
 
       if (payout === 0) {
-        console.log("   No payout to claim (lost the game)");
+        console.log("   No payout to claim (lost the game), closing it for rent");
+        const closeIx = await program.methods
+          .closeLostGame({
+            handle: handleToBuffer(payoutHandle),
+            plaintext: plaintextToBuffer(result.plaintext),
+          })
+          .accounts({
+            closer: wallet.publicKey,
+            player: wallet.publicKey,
+            game: gamePda,
+            mintConfig: null,
+            legs: null,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          } as any)
+          .instruction();
+
+        const closeTx = new Transaction();
+        result.ed25519Instructions.forEach((ix) => closeTx.add(ix));
+        closeTx.add(closeIx);
+        const { blockhash } = await connection.getLatestBlockhash();
+        closeTx.recentBlockhash = blockhash;
+        closeTx.feePayer = wallet.publicKey;
+
+        const signedCloseTx = await provider.wallet.signTransaction(closeTx);
+        const closeSig = await connection.sendRawTransaction(signedCloseTx.serialize());
+        await connection.confirmTransaction(closeSig, "confirmed");
+        expect(await connection.getAccountInfo(gamePda)).to.be.null;
+        console.log("   ✅ Lost game closed, rent refunded:", closeSig);
         return;
       }
 
@@ -753,6 +1065,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
             casinoVault: casinoVaultPda,
             lpVault: lpVaultPda,
             game: gamePda,
+            legs: null,
             instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
             incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -775,9 +1088,9 @@ describe("Casino & Church - Inco Privacy Tests", () => {
         console.log("   🎉 Claimed:", sig);
         console.log(`   Received: ${payout / LAMPORTS_PER_SOL} SOL`);
 
-        // Verify game is marked as claimed
-        const updatedGame = await program.account.gameResult.fetch(gamePda);
-        console.log("   Game claimed status:", updatedGame.claimed);
+        // Verify the game account was closed and its rent refunded
+        const closedGame = await connection.getAccountInfo(gamePda);
+        console.log("   Game account closed:", closedGame === null);
       } catch (e: any) {
         console.log("   Claim failed:", e.message?.slice(0, 200));
         console.log("   Full error:", e);
@@ -815,6 +1128,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
   //           .accounts({
   //             player: wallet.publicKey,
   //             admin: adminPda,
+  //             gameConfig: deriveGameConfigPda(GAME_ID.coinflip),
   //             casinoVault: casinoVaultPda,
   //             game: gamePda,
   //             incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -860,6 +1174,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
   //         .accounts({
   //           player: wallet.publicKey,
   //           admin: adminPda,
+  //           gameConfig: deriveGameConfigPda(GAME_ID.coinflip),
   //           casinoVault: casinoVaultPda,
   //           game: gamePda,
   //           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -901,6 +1216,7 @@ describe("Casino & Church - Inco Privacy Tests", () => {
   //         .accounts({
   //           player: wallet.publicKey,
   //           admin: adminPda,
+  //           gameConfig: deriveGameConfigPda(GAME_ID.coinflip),
   //           casinoVault: casinoVaultPda,
   //           game: gamePda,
   //           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
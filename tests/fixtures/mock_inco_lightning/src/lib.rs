@@ -0,0 +1,252 @@
+//! Plaintext stand-in for the Inco Lightning program.
+//!
+//! Deployed under the real Inco program id so the casino's CPIs resolve unchanged in
+//! in-process tests (litesvm). Instruction names and arguments match the
+//! `inco-lightning` CPI crate, but every handle simply IS its plaintext value:
+//! - `new_euint128` reads the "ciphertext" as little-endian plaintext bytes
+//! - arithmetic wraps, division/remainder by zero yield 0
+//! - comparisons return Ebool(0 | 1)
+//! - `allow` is a no-op
+//! - `is_validsignature` checks each plaintext equals its handle (no Ed25519 check)
+//!
+//! Never deploy this anywhere but a local test validator. It lives outside the Anchor
+//! workspace so `anchor build`, `anchor deploy` and `anchor keys sync` never touch it;
+//! build it with `yarn build:mock-inco`, which writes target/deploy/mock_inco_lightning.so.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{compute_units::sol_remaining_compute_units, hash::hashv};
+
+pub mod types;
+pub use types::*;
+
+declare_id!("5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj");
+
+#[program]
+pub mod mock_inco_lightning {
+    use super::*;
+
+    pub fn new_euint128(
+        _ctx: Context<Operation>,
+        ciphertext: Vec<u8>,
+        _input_type: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(decode_le(&ciphertext)?))
+    }
+
+    pub fn as_euint128(_ctx: Context<Operation>, value: u128) -> Result<Euint128> {
+        Ok(Euint128(value))
+    }
+
+    pub fn as_ebool(_ctx: Context<Operation>, value: bool) -> Result<Ebool> {
+        Ok(Ebool(value as u128))
+    }
+
+    /// Deterministic per slot and call site: hashes the slot, the signer and the remaining
+    /// compute budget, so consecutive calls in one transaction return different values.
+    pub fn e_rand(ctx: Context<Operation>, _scalar_byte: u8) -> Result<Euint128> {
+        let slot = Clock::get()?.slot;
+        let remaining = sol_remaining_compute_units();
+        let hash = hashv(&[
+            &slot.to_le_bytes(),
+            ctx.accounts.signer.key.as_ref(),
+            &remaining.to_le_bytes(),
+        ]);
+        let bytes: [u8; 16] = hash.to_bytes()[..16].try_into().unwrap();
+        Ok(Euint128(u128::from_le_bytes(bytes)))
+    }
+
+    pub fn e_add(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.wrapping_add(rhs.0)))
+    }
+
+    pub fn e_sub(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.wrapping_sub(rhs.0)))
+    }
+
+    pub fn e_mul(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.wrapping_mul(rhs.0)))
+    }
+
+    pub fn e_div(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.checked_div(rhs.0).unwrap_or(0)))
+    }
+
+    pub fn e_rem(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.checked_rem(rhs.0).unwrap_or(0)))
+    }
+
+    pub fn e_min(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.min(rhs.0)))
+    }
+
+    pub fn e_max(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(Euint128(lhs.0.max(rhs.0)))
+    }
+
+    pub fn e_eq(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        Ok(Ebool((lhs.0 == rhs.0) as u128))
+    }
+
+    pub fn e_ne(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        Ok(Ebool((lhs.0 != rhs.0) as u128))
+    }
+
+    pub fn e_ge(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        Ok(Ebool((lhs.0 >= rhs.0) as u128))
+    }
+
+    pub fn e_gt(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        Ok(Ebool((lhs.0 > rhs.0) as u128))
+    }
+
+    pub fn e_le(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        Ok(Ebool((lhs.0 <= rhs.0) as u128))
+    }
+
+    pub fn e_lt(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        Ok(Ebool((lhs.0 < rhs.0) as u128))
+    }
+
+    pub fn e_select(
+        _ctx: Context<Operation>,
+        condition: Ebool,
+        if_true: Euint128,
+        if_false: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(if condition.0 != 0 { if_true } else { if_false })
+    }
+
+    pub fn allow(
+        _ctx: Context<Allow>,
+        _handle: u128,
+        _value: bool,
+        _allowed_address: Pubkey,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn is_validsignature(
+        _ctx: Context<VerifySignature>,
+        expected_signer_count: u8,
+        handles: Option<Vec<Vec<u8>>>,
+        plaintext_values: Option<Vec<Vec<u8>>>,
+    ) -> Result<()> {
+        let handles = handles.unwrap_or_default();
+        let plaintexts = plaintext_values.unwrap_or_default();
+        require!(expected_signer_count > 0, MockIncoError::InvalidSignature);
+        require!(handles.len() == plaintexts.len(), MockIncoError::InvalidSignature);
+        for (handle, plaintext) in handles.iter().zip(plaintexts.iter()) {
+            require!(
+                decode_le(handle)? == decode_le(plaintext)?,
+                MockIncoError::InvalidSignature
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Operation<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Allow<'info> {
+    /// CHECK: the real program stores the allowance here; the mock ignores it
+    #[account(mut)]
+    pub allowance_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: address being granted decrypt access
+    pub allowed_address: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifySignature<'info> {
+    /// CHECK: instructions sysvar; the mock does not look for an Ed25519 instruction
+    pub instructions: UncheckedAccount<'info>,
+    pub signer: Signer<'info>,
+}
+
+#[error_code]
+pub enum MockIncoError {
+    #[msg("Value is wider than 128 bits")]
+    ValueTooWide,
+    #[msg("Plaintext does not match handle")]
+    InvalidSignature,
+}
+
+/// Little-endian bytes (up to 16) to u128
+fn decode_le(bytes: &[u8]) -> Result<u128> {
+    require!(bytes.len() <= 16, MockIncoError::ValueTooWide);
+    let mut buf = [0u8; 16];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    Ok(u128::from_le_bytes(buf))
+}
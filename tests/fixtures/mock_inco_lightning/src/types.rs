@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Encrypted u128 handle; in the mock the handle is the plaintext
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Euint128(pub u128);
+
+/// Encrypted bool handle (0 or 1)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ebool(pub u128);